@@ -0,0 +1,237 @@
+use std::{
+    fs::{create_dir_all, File},
+    io::{self, Read, Seek, SeekFrom},
+    path::{Component, Path, PathBuf},
+    time::{Duration, UNIX_EPOCH},
+};
+
+use crate::{
+    archive::{safe_join, Archived, OnUnpacked, Visitor},
+    entry::EntryType,
+    Entries, Entry, Error, Result,
+};
+
+const AR_MAGIC: &[u8] = b"!<arch>\n";
+const HEADER_SIZE: u64 = 60;
+
+/// A member of an `ar` archive, i.e. a file stored at a given offset.
+#[derive(Debug, Clone)]
+pub(crate) struct Member {
+    pub(crate) name: String,
+    pub(crate) offset: u64,
+    pub(crate) size: u64,
+    mode: Option<u32>,
+    mtime: Option<u64>,
+}
+
+/// Reads the list of members of an `ar` archive, skipping the special
+/// members (symbol table and long names table) of GNU archives.
+pub(crate) fn read_members<R: Read + Seek>(reader: &mut R) -> Result<Vec<Member>> {
+    let mut magic = [0; AR_MAGIC.len()];
+    reader.seek(SeekFrom::Start(0))?;
+    reader
+        .read_exact(&mut magic)
+        .map_err(|_| Error::invalid_archive("invalid ar header"))?;
+    if magic != AR_MAGIC {
+        Err(Error::invalid_archive("invalid ar header"))?;
+    }
+
+    let len = reader.seek(SeekFrom::End(0))?;
+    let mut offset = AR_MAGIC.len() as u64;
+    let mut members = vec![];
+    while offset + HEADER_SIZE <= len {
+        let mut header = [0; HEADER_SIZE as usize];
+        reader.seek(SeekFrom::Start(offset))?;
+        reader.read_exact(&mut header)?;
+        if &header[58..] != b"`\n" {
+            Err(Error::invalid_archive("invalid ar member header"))?;
+        }
+
+        let field = |start: usize, end: usize| {
+            std::str::from_utf8(&header[start..end])
+                .map(str::trim)
+                .map_err(|_| Error::invalid_archive("invalid ar member header"))
+        };
+        let name = field(0, 16)?.trim_end_matches('/').to_string();
+        let mtime = field(16, 28)?.parse::<u64>().ok();
+        let mode = u32::from_str_radix(field(40, 48)?, 8).ok();
+        let size = field(48, 58)?
+            .parse::<u64>()
+            .map_err(|_| Error::invalid_archive("invalid ar member size"))?;
+
+        let data = offset + HEADER_SIZE;
+        if !name.is_empty() {
+            let mut components = Path::new(&name).components();
+            if !matches!(
+                (components.next(), components.next()),
+                (Some(Component::Normal(_)), None)
+            ) {
+                Err(Error::invalid_archive("invalid ar member name"))?;
+            }
+            members.push(Member {
+                name,
+                offset: data,
+                size,
+                mode,
+                mtime,
+            });
+        }
+        // member data is aligned on even offsets
+        offset = data + size + size % 2;
+    }
+    Ok(members)
+}
+
+/// Reader over a range of an underlying reader, e.g. an archive
+/// stored as a member of an `ar` archive.
+pub(crate) struct Slice<R> {
+    inner: R,
+    start: u64,
+    len: u64,
+    pos: u64,
+}
+
+impl<R: Seek> Slice<R> {
+    pub(crate) fn new(mut inner: R, start: u64, len: u64) -> io::Result<Self> {
+        inner.seek(SeekFrom::Start(start))?;
+        let pos = 0;
+        Ok(Slice {
+            inner,
+            start,
+            len,
+            pos,
+        })
+    }
+}
+
+impl<R: Read> Read for Slice<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let remaining = self.len.saturating_sub(self.pos);
+        let max = buf.len().min(remaining.try_into().unwrap_or(usize::MAX));
+        let len = self.inner.read(&mut buf[..max])?;
+        self.pos += len as u64;
+        Ok(len)
+    }
+}
+
+impl<R: Seek> Seek for Slice<R> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let pos = match pos {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::End(offset) => self.len.checked_add_signed(offset),
+            SeekFrom::Current(offset) => self.pos.checked_add_signed(offset),
+        }
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "invalid seek position"))?;
+        self.inner.seek(SeekFrom::Start(self.start + pos))?;
+        self.pos = pos;
+        Ok(pos)
+    }
+}
+
+fn to_entry(index: usize, member: &Member) -> Entry {
+    Entry {
+        index,
+        path: PathBuf::from(&member.name),
+        size: member.size,
+        compressed_size: None,
+        entry_type: EntryType::File,
+        creator_os: None,
+        unix_mode: member.mode,
+        modified: member
+            .mtime
+            .map(|mtime| UNIX_EPOCH + Duration::from_secs(mtime)),
+        uid: None,
+        gid: None,
+        uname: None,
+        gname: None,
+    }
+}
+
+/// `ar` archive backend (e.g. debian packages)
+pub(crate) struct Ar<R> {
+    reader: R,
+    members: Vec<Member>,
+}
+
+impl<R: Read + Seek> Ar<R> {
+    pub(crate) fn new(mut reader: R) -> Result<Self> {
+        let members = read_members(&mut reader)?;
+        Ok(Ar { reader, members })
+    }
+
+    fn member_reader(&mut self, entry: &Entry) -> Result<io::Take<&mut R>> {
+        let member = self
+            .members
+            .get(entry.index())
+            .filter(|member| Path::new(&member.name) == entry.path())
+            .ok_or(Error::FileNotFound)?;
+        self.reader.seek(SeekFrom::Start(member.offset))?;
+        Ok(self.reader.by_ref().take(member.size))
+    }
+}
+
+impl<R: Read + Seek> Archived for Ar<R> {
+    fn unpack(&mut self, dest: &Path, on_unpacked: &mut OnUnpacked) -> Result<()> {
+        for index in 0..self.members.len() {
+            let entry = to_entry(index, &self.members[index]);
+            self.unpack_entry(&entry, dest)?;
+            on_unpacked();
+        }
+        Ok(())
+    }
+
+    fn entries(&mut self) -> Result<Entries> {
+        let entries = self
+            .members
+            .iter()
+            .enumerate()
+            .map(|(index, member)| Ok(to_entry(index, member)));
+        Ok(Box::new(entries))
+    }
+
+    fn unpack_entry(&mut self, entry: &Entry, dest: &Path) -> Result<()> {
+        create_dir_all(dest)?;
+        let outpath = safe_join(dest, entry.path())?;
+        let mut reader = self.member_reader(entry)?;
+        let mut outfile = File::create(&outpath)?;
+        io::copy(&mut reader, &mut outfile)?;
+        if let Some(modified) = entry.modified {
+            outfile.set_modified(modified)?;
+        }
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            if let Some(mode) = entry.unix_mode {
+                outfile.set_permissions(std::fs::Permissions::from_mode(mode))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn walk(&mut self, visit: &mut Visitor) -> Result<()> {
+        for index in 0..self.members.len() {
+            let entry = to_entry(index, &self.members[index]);
+            let mut reader = self.member_reader(&entry)?;
+            if visit(entry, &mut reader)?.is_break() {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    fn entry_reader(&mut self, entry: &Entry) -> Result<Box<dyn Read + '_>> {
+        Ok(Box::new(self.member_reader(entry)?))
+    }
+
+    fn verify_entry(&mut self, entry: &Entry) -> Result<bool> {
+        // ar archives do not store any checksum
+        self.member_reader(entry)?;
+        Ok(true)
+    }
+
+    fn entry_count_hint(&self) -> Option<usize> {
+        Some(self.members.len())
+    }
+}
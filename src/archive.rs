@@ -1,34 +1,300 @@
 use std::{
     borrow::Cow,
-    fs::File,
-    path::{Path, PathBuf},
+    collections::{BTreeMap, BTreeSet},
+    ffi::OsStr,
+    fs::{create_dir_all, File},
+    io::{self, Cursor, Read, Seek, SeekFrom, Write},
+    ops::ControlFlow,
+    path::{Component, Path, PathBuf},
+    sync::{
+        mpsc::{sync_channel, Receiver},
+        Arc, Mutex, MutexGuard,
+    },
+    thread,
+    time::SystemTime,
 };
 
+use filetime::{set_symlink_file_times, FileTime};
+
+#[cfg(feature = "zip")]
+use crate::zip::{read_creator_os, IndexProgressFn, Zip};
+
 #[cfg(feature = "zip")]
-use zip::ZipArchive as Zip;
+use std::rc::Rc;
+
+#[cfg(feature = "crx")]
+use crate::zip::check_crx_header;
+
+#[cfg(feature = "deb")]
+use crate::ar::{Ar, Slice};
+
+#[cfg(feature = "sevenz")]
+use crate::sevenz::SevenZ;
+
+#[cfg(feature = "rpm")]
+use crate::{cpio::Cpio, rpm};
+
+#[cfg(any(feature = "tar", feature = "zstd"))]
+use std::io::BufReader;
 
 #[cfg(feature = "tar")]
-use tar::Archive as Tar;
+use crate::tar::Tar;
+
+#[cfg(any(feature = "gzip", feature = "bzip2", feature = "xz", feature = "zstd"))]
+use crate::compressed::Compressed;
 
-#[cfg(all(feature = "tar", feature = "gzip"))]
+#[cfg(feature = "gzip")]
 use flate2::read::GzDecoder;
 
-#[cfg(all(feature = "tar", feature = "bzip2"))]
+#[cfg(feature = "bzip2")]
 use bzip2::read::BzDecoder;
 
-#[cfg(all(feature = "tar", feature = "xz2"))]
+#[cfg(feature = "xz")]
 use xz2::read::XzDecoder;
 
-#[cfg(all(feature = "tar", feature = "zstd"))]
+#[cfg(feature = "zstd")]
 use zstd::stream::Decoder as ZstdDecoder;
 
-use crate::{Entries, Entry, Error, FindEntries, Format, Result};
+#[cfg(all(feature = "tar", feature = "lzip"))]
+use crate::lzip::LzDecoder;
+
+use crate::{
+    entry::unpack_path, format::MAGIC_BYTES_LEN, ArchiveOptions, ArchiveScan, Entries, Entry,
+    EntryCursor, EntryType, Error, FindEntries, Format, Result, Unmatched,
+};
+
+/// callback used to visit the entries of an archive along with their contents
+pub(crate) type Visitor<'a> = dyn 'a + FnMut(Entry, &mut dyn Read) -> Result<ControlFlow<()>>;
+
+/// callback called by [`Archived::unpack`] every time an entry has been unpacked
+pub(crate) type OnUnpacked<'a> = dyn 'a + FnMut();
 
 /// private interface for an archive backend (zip or archive)
 pub(crate) trait Archived {
-    fn unpack(&mut self, dest: &Path) -> Result<()>;
+    fn unpack(&mut self, dest: &Path, on_unpacked: &mut OnUnpacked) -> Result<()>;
     fn entries(&mut self) -> Result<Entries>;
     fn unpack_entry(&mut self, entry: &Entry, dest: &Path) -> Result<()>;
+    fn walk(&mut self, visit: &mut Visitor) -> Result<()>;
+    fn entry_reader(&mut self, entry: &Entry) -> Result<Box<dyn Read + '_>>;
+    fn verify_entry(&mut self, entry: &Entry) -> Result<bool>;
+
+    /// number of entries, if known without reading the whole archive
+    fn entry_count_hint(&self) -> Option<usize> {
+        None
+    }
+
+    /// whether an entry has the given path, stopping at the first match
+    fn contains(&mut self, path: &Path) -> Result<bool> {
+        for entry in self.entries()? {
+            if entry?.path() == path {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+}
+
+/// backend decorator adding the path of the archive to the invalid or
+/// unsupported archive errors returned by the actual backend
+struct WithPath {
+    inner: Box<dyn Archived>,
+    path: PathBuf,
+}
+
+impl Archived for WithPath {
+    fn unpack(&mut self, dest: &Path, on_unpacked: &mut OnUnpacked) -> Result<()> {
+        let result = self.inner.unpack(dest, on_unpacked);
+        result.map_err(|err| err.with_path(&self.path))
+    }
+
+    fn entries(&mut self) -> Result<Entries> {
+        let path = &self.path;
+        let entries = self.inner.entries().map_err(|err| err.with_path(path))?;
+        Ok(Box::new(
+            entries.map(|entry| entry.map_err(|err| err.with_path(path))),
+        ))
+    }
+
+    fn unpack_entry(&mut self, entry: &Entry, dest: &Path) -> Result<()> {
+        let result = self.inner.unpack_entry(entry, dest);
+        result.map_err(|err| err.with_path(&self.path))
+    }
+
+    fn walk(&mut self, visit: &mut Visitor) -> Result<()> {
+        let result = self.inner.walk(visit);
+        result.map_err(|err| err.with_path(&self.path))
+    }
+
+    fn entry_reader(&mut self, entry: &Entry) -> Result<Box<dyn Read + '_>> {
+        let result = self.inner.entry_reader(entry);
+        result.map_err(|err| err.with_path(&self.path))
+    }
+
+    fn verify_entry(&mut self, entry: &Entry) -> Result<bool> {
+        let result = self.inner.verify_entry(entry);
+        result.map_err(|err| err.with_path(&self.path))
+    }
+
+    fn entry_count_hint(&self) -> Option<usize> {
+        self.inner.entry_count_hint()
+    }
+
+    fn contains(&mut self, path: &Path) -> Result<bool> {
+        let result = self.inner.contains(path);
+        result.map_err(|err| err.with_path(&self.path))
+    }
+}
+
+/// Joins the path of an entry to the destination directory, the leading `/`
+/// of absolute paths being stripped. Returns [`Error::UnsafePath`] if the
+/// entry would end up outside of the destination directory, either because
+/// of `..` components or because one of its parents is a symbolic link
+/// pointing outside of it.
+pub(crate) fn safe_join(dest: &Path, entry_path: &Path) -> Result<PathBuf> {
+    let unsafe_path = || Error::UnsafePath(entry_path.to_path_buf());
+
+    let mut outpath = dest.to_path_buf();
+    let mut depth = 0;
+    for component in unpack_path(entry_path).components() {
+        match component {
+            Component::Normal(name) => {
+                outpath.push(name);
+                depth += 1;
+            }
+            Component::CurDir => {}
+            Component::ParentDir if depth > 0 => {
+                outpath.pop();
+                depth -= 1;
+            }
+            _ => Err(unsafe_path())?,
+        }
+    }
+
+    if depth == 0 {
+        // e.g. `./`, i.e. the destination directory itself
+        return Ok(outpath);
+    }
+
+    // the parents that already exist are resolved to detect symbolic links
+    let parent = outpath
+        .parent()
+        .and_then(|parent| parent.canonicalize().ok());
+    if let (Ok(dest), Some(parent)) = (dest.canonicalize(), parent) {
+        if !parent.starts_with(dest) {
+            Err(unsafe_path())?;
+        }
+    }
+    Ok(outpath)
+}
+
+/// Checks that the target of a symbolic link is a relative path which,
+/// resolved from the directory containing the link, does not point outside
+/// of the destination directory.
+fn check_symlink_target(entry_path: &Path, target: &Path) -> Result<()> {
+    if target.has_root() {
+        Err(Error::invalid_archive("absolute symbolic link target"))?;
+    }
+    let parent = unpack_path(entry_path).parent().unwrap_or(Path::new(""));
+    let mut depth = 0;
+    for component in parent.components().chain(target.components()) {
+        match component {
+            Component::Normal(_) => depth += 1,
+            Component::CurDir => {}
+            Component::ParentDir if depth > 0 => depth -= 1,
+            _ => Err(Error::invalid_archive(
+                "symbolic link target outside of the destination directory",
+            ))?,
+        }
+    }
+    Ok(())
+}
+
+/// Creates a symbolic link entry in the destination directory, replacing
+/// any existing file. Symbolic links are skipped on platforms other than
+/// unix.
+pub(crate) fn unpack_symlink(entry_path: &Path, target: &Path, dest: &Path) -> Result<()> {
+    check_symlink_target(entry_path, target)?;
+    let outpath = safe_join(dest, entry_path)?;
+    if let Some(p) = outpath.parent() {
+        create_dir_all(p)?;
+    }
+
+    #[cfg(unix)]
+    {
+        if outpath.symlink_metadata().is_ok() {
+            std::fs::remove_file(&outpath)?;
+        }
+        std::os::unix::fs::symlink(target, &outpath)?;
+    }
+
+    #[cfg(all(not(unix), feature = "tracing"))]
+    tracing::warn!(
+        "skipping symbolic link '{}' not supported on this platform",
+        entry_path.display()
+    );
+
+    Ok(())
+}
+
+/// Creates a hard link entry in the destination directory, replacing any
+/// existing file. The entry it points to must have been unpacked already.
+pub(crate) fn unpack_hardlink(entry_path: &Path, target: &Path, dest: &Path) -> Result<()> {
+    // the target is relative to the root of the archive
+    let src = safe_join(dest, target)?;
+    let outpath = safe_join(dest, entry_path)?;
+    if let Some(p) = outpath.parent() {
+        create_dir_all(p)?;
+    }
+    if outpath.symlink_metadata().is_ok() {
+        std::fs::remove_file(&outpath)?;
+    }
+    std::fs::hard_link(src, outpath)?;
+    Ok(())
+}
+
+/// Sets the access and modification times of an unpacked entry, without
+/// following symbolic links.
+fn set_fixed_time(dest: &Path, entry_path: &Path, time: FileTime) -> Result<()> {
+    let outpath = safe_join(dest, entry_path)?;
+    set_symlink_file_times(outpath, time, time).map_err(|err| Error::from(err).in_entry(entry_path))
+}
+
+/// Extracts an entry visited along with its contents to the destination
+/// directory. Entries that are neither files, directories nor links are
+/// skipped, since their contents are not available.
+pub(crate) fn extract(entry: &Entry, reader: &mut dyn Read, dest: &Path) -> Result<()> {
+    extract_entry(entry, reader, dest).map_err(|err| err.in_entry(entry.path()))
+}
+
+fn extract_entry(entry: &Entry, reader: &mut dyn Read, dest: &Path) -> Result<()> {
+    if let Some(target) = entry.symlink_target() {
+        return unpack_symlink(entry.path(), target, dest);
+    }
+    if let Some(target) = entry.hardlink_target() {
+        return unpack_hardlink(entry.path(), target, dest);
+    }
+    let outpath = safe_join(dest, entry.path())?;
+    if entry.is_dir() {
+        create_dir_all(&outpath)?;
+    } else if entry.is_file() {
+        if let Some(p) = outpath.parent() {
+            create_dir_all(p)?;
+        }
+        let mut outfile = File::create(&outpath)?;
+        io::copy(reader, &mut outfile)?;
+        if let Some(modified) = entry.modified {
+            outfile.set_modified(modified)?;
+        }
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            if let Some(mode) = entry.unix_mode {
+                outfile.set_permissions(std::fs::Permissions::from_mode(mode))?;
+            }
+        }
+    }
+    Ok(())
 }
 
 #[derive(Debug)]
@@ -41,6 +307,79 @@ pub(crate) enum Storage {
         temp: tempfile::TempDir,
         file_name: std::ffi::OsString,
     },
+    #[cfg(feature = "deb")]
+    ArMember {
+        path: PathBuf,
+        name: String,
+        offset: u64,
+        size: u64,
+    },
+    #[cfg(feature = "download")]
+    Stream {
+        url: String,
+    },
+    InMemory {
+        data: Arc<[u8]>,
+    },
+    Reader {
+        reader: SharedReader,
+    },
+}
+
+/// reader over the contents of an archive
+pub(crate) trait ReadSeek: Read + Seek {}
+
+impl<T: Read + Seek> ReadSeek for T {}
+
+/// reader shared by all the backends successively created for an archive
+/// opened from a reader, each of them keeping track of its own position
+#[derive(Clone)]
+pub(crate) struct SharedReader {
+    inner: Arc<Mutex<dyn ReadSeek + Send>>,
+    pos: u64,
+}
+
+impl SharedReader {
+    fn lock(&self) -> io::Result<MutexGuard<'_, dyn ReadSeek + Send + 'static>> {
+        self.inner
+            .lock()
+            .map_err(|_| io::Error::other("poisoned reader"))
+    }
+}
+
+impl std::fmt::Debug for SharedReader {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt.debug_struct("SharedReader")
+            .field("pos", &self.pos)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Read for SharedReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut inner = self.lock()?;
+        inner.seek(SeekFrom::Start(self.pos))?;
+        let len = inner.read(buf)?;
+        drop(inner);
+        self.pos += len as u64;
+        Ok(len)
+    }
+}
+
+impl Seek for SharedReader {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let pos = match pos {
+            SeekFrom::Current(offset) => SeekFrom::Start(
+                self.pos
+                    .checked_add_signed(offset)
+                    .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "invalid seek"))?,
+            ),
+            pos => pos,
+        };
+        let pos = self.lock()?.seek(pos)?;
+        self.pos = pos;
+        Ok(pos)
+    }
 }
 
 impl Storage {
@@ -51,13 +390,37 @@ impl Storage {
             Storage::FileInTempDirectory { temp, file_name } => {
                 Cow::Owned(temp.path().join(file_name))
             }
+            #[cfg(feature = "deb")]
+            Storage::ArMember { path, name, .. } => Cow::Owned(path.join(name)),
+            #[cfg(feature = "download")]
+            Storage::Stream { url } => Cow::Owned(PathBuf::from(url)),
+            Storage::InMemory { .. } | Storage::Reader { .. } => Cow::Borrowed(Path::new("")),
+        }
+    }
+
+    pub(crate) fn open(&self) -> Result<Box<dyn ReadSeek>> {
+        match self {
+            #[cfg(feature = "deb")]
+            Storage::ArMember {
+                path, offset, size, ..
+            } => Ok(Box::new(Slice::new(File::open(path)?, *offset, *size)?)),
+            #[cfg(feature = "download")]
+            Storage::Stream { .. } => Err(Error::unsupported_archive(
+                "a streamed archive cannot be read randomly",
+            )),
+            Storage::InMemory { data } => Ok(Box::new(Cursor::new(data.clone()))),
+            Storage::Reader { reader } => Ok(Box::new(SharedReader {
+                inner: reader.inner.clone(),
+                pos: 0,
+            })),
+            _ => Ok(Box::new(File::open(self.as_path())?)),
         }
     }
 
     #[cfg(feature = "download")]
     pub(crate) fn create(&self) -> Result<File> {
-        if let Storage::FileOnDisk { path } = self {
-            std::fs::create_dir_all(path)?;
+        if let Some(parent) = self.as_path().parent() {
+            std::fs::create_dir_all(parent)?;
         }
         Ok(File::create(self.as_path())?)
     }
@@ -68,31 +431,157 @@ impl Storage {
 /// # Supported Formats
 ///
 /// - `sample.zip` (requires the zip feature).
+/// - `sample.epub` (EPUB e-books are zip archives, requires the `zip` feature).
+/// - `sample.crx` (Chrome extensions are prefixed zip archives, requires the `crx` feature).
 /// - `sample.tar` (requires the `tar` feature).
+/// - `sample.gem` (Ruby gems are plain tar archives, requires the `tar` feature).
+/// - `sample.deb` (Debian packages are `ar` archives, requires the `deb` feature,
+///   see also [`DebPackage`](crate::DebPackage)).
 /// - `sample.tgz` or `sample.tar.gz` (requires `tar` and `gzip` features).
 /// - `sample.tar.xz` (requires `tar` and `xz` features).
 /// - `sample.tar.bz2` (requires `tar` and `bzip` features).
 /// - `sample.tar.zstd` or `sample.tar.zst` (requires `tar` and `zstd` features).
+/// - `sample.tar.lz` (requires `tar` and `lzip` features).
+/// - `sample.7z` (requires the `sevenz` feature).
+/// - `sample.rpm` (RPM packages wrap a cpio archive, requires the `rpm` feature
+///   along with the feature of their payload compression, e.g. `xz`).
+/// - `sample.snap` (Snapcraft packages are SquashFS images, which are
+///   recognized but not supported).
+/// - `sample.iso` (ISO 9660 images, recognized but not supported).
+/// - `sample.txt.gz`, `sample.txt.xz`, `sample.txt.bz2` or `sample.txt.zst` (single
+///   compressed files, seen as an archive containing a single `sample.txt` entry,
+///   requires the `gzip`, `xz`, `bzip` or `zstd` feature respectively).
 pub struct Archive {
     format: Format,
     storage: Storage,
     archived: Option<Box<dyn Archived>>,
+    options: ArchiveOptions,
+    #[cfg(feature = "zip")]
+    index_progress: Option<Rc<IndexProgressFn>>,
+    /// host system of the zip entries, read once from the central directory
+    #[cfg(feature = "zip")]
+    zip_creator_os: Option<Rc<[u8]>>,
+}
+
+/// junk prefixes that some buggy tools prepend to archives (byte order marks)
+const JUNK_PREFIXES: [&[u8]; 3] = [b"\xEF\xBB\xBF", b"\xFF\xFE", b"\xFE\xFF"];
+
+/// skips a known junk prefix at the start of a file, if any
+fn skip_junk_prefix(file: &mut dyn ReadSeek) -> Result<()> {
+    let mut head = vec![];
+    file.take(3).read_to_end(&mut head)?;
+    let skip = JUNK_PREFIXES
+        .iter()
+        .find(|prefix| head.starts_with(prefix))
+        .map_or(0, |prefix| prefix.len());
+    file.seek(SeekFrom::Start(skip as u64))?;
+    Ok(())
+}
+
+/// checks that the magic bytes at the current position of a file match
+/// the format of the archive, leaving the position unchanged
+fn check_magic_bytes(file: &mut dyn ReadSeek, format: &Format) -> Result<()> {
+    let start = file.stream_position()?;
+    let mut magic = vec![];
+    file.take(MAGIC_BYTES_LEN as u64).read_to_end(&mut magic)?;
+    file.seek(SeekFrom::Start(start))?;
+    if !format.matches_magic_bytes(&magic) {
+        Err(Error::invalid_archive(format!(
+            "contents do not match the {format:?} format"
+        )))?;
+    }
+    Ok(())
+}
+
+/// creates the backend of a tar based archive, which only requires
+/// the contents of the archive to be read sequentially
+#[allow(unused)]
+fn tar_archived(
+    format: &Format,
+    reader: impl Read + 'static,
+    options: &ArchiveOptions,
+) -> Result<Box<dyn Archived>> {
+    match format {
+        #[cfg(feature = "tar")]
+        // NOTE: the decoders below already buffer their input
+        Format::Tar => Ok(Box::new(Tar::new(BufReader::new(reader)))),
+
+        #[cfg(all(feature = "tar", feature = "gzip"))]
+        Format::TarGzip => Ok(Box::new(Tar::new(GzDecoder::new(reader)))),
+
+        #[cfg(all(feature = "tar", feature = "bzip2"))]
+        Format::TarBzip2 => Ok(Box::new(Tar::new(BzDecoder::new(reader)))),
+
+        #[cfg(all(feature = "tar", feature = "xz"))]
+        Format::TarXz2 => Ok(Box::new(Tar::new(XzDecoder::new(reader)))),
+
+        #[cfg(all(feature = "tar", feature = "zstd"))]
+        Format::TarZstd => Ok(Box::new(Tar::new(zstd_decoder(reader, options)?))),
+
+        #[cfg(all(feature = "tar", feature = "lzip"))]
+        Format::TarLzip => Ok(Box::new(Tar::new(LzDecoder::new(reader)?))),
+
+        _ => Err(Error::unsupported_archive(
+            "unsupported format, did you enable the proper feature?",
+        )),
+    }
+}
+
+/// creates a zstd decoder, using the dictionary of the options if any
+#[cfg(feature = "zstd")]
+fn zstd_decoder<R: Read>(
+    reader: R,
+    options: &ArchiveOptions,
+) -> Result<ZstdDecoder<'static, BufReader<R>>> {
+    let reader = BufReader::new(reader);
+    match &options.zstd_dictionary {
+        Some(dict) => Ok(ZstdDecoder::with_dictionary(reader, dict)?),
+        None => Ok(ZstdDecoder::with_buffer(reader)?),
+    }
 }
 
 impl Archive {
+    #[cfg(any(feature = "download", feature = "deb"))]
     pub(crate) fn new(storage: Storage) -> Result<Self> {
-        let archived = None;
         let format = Format::infer_from_file_extension(storage.as_path());
-        if !format.is_archive() {
-            Err(Error::UnsupportedArchive(
+        if format == Format::Unknown {
+            Err(Error::UnrecognizedFormat(storage.as_path().to_path_buf()))?;
+        }
+        Archive::with_format(storage, format)
+    }
+
+    pub(crate) fn with_format(storage: Storage, format: Format) -> Result<Self> {
+        let archived = None;
+        let options = ArchiveOptions::default();
+        if format == Format::Squashfs {
+            Err(Error::unsupported_archive(
+                "squashfs images (e.g. snap packages) are filesystems, not archives, \
+                 consider using the squashfs crate instead",
+            )
+            .with_path(&storage.as_path()))?;
+        }
+        if format == Format::Iso {
+            Err(Error::unsupported_archive(
+                "ISO images require a dedicated library, consider using the iso9660 crate instead",
+            )
+            .with_path(&storage.as_path()))?;
+        }
+        if !format.is_supported() {
+            Err(Error::unsupported_archive(
                 "unsupported format, did you enable the proper feature?",
-            ))?;
+            )
+            .with_path(&storage.as_path()))?;
         }
 
         Ok(Archive {
             format,
             storage,
             archived,
+            options,
+            #[cfg(feature = "zip")]
+            index_progress: None,
+            #[cfg(feature = "zip")]
+            zip_creator_os: None,
         })
     }
 
@@ -101,6 +590,11 @@ impl Archive {
     /// The format of the archive will be inferred from the file
     /// extension. See [`infer_from_file_extension`](Format::infer_from_file_extension).
     ///
+    /// Returns [`Error::ArchiveNotFound`] if the file does not exist,
+    /// [`Error::UnrecognizedFormat`] if its extension does not correspond to
+    /// any known format, and [`Error::UnsupportedArchive`] if the format is
+    /// known but not supported by the current build.
+    ///
     /// # Arguments:
     ///
     /// - `path`: the path to the archive file to open
@@ -114,211 +608,1395 @@ impl Archive {
     /// ```
     ///
     pub fn open(path: impl AsRef<Path>) -> Result<Self> {
-        let path = path.as_ref().to_path_buf();
-        let storage = Storage::FileOnDisk { path };
-        Archive::new(storage)
+        let path = path.as_ref();
+        if !path.exists() {
+            Err(Error::ArchiveNotFound(path.to_path_buf()))?;
+        }
+        let format = Format::infer_from_file_extension(path);
+        if format == Format::Unknown {
+            Err(Error::UnrecognizedFormat(path.to_path_buf()))?;
+        }
+        Archive::open_with_format(path, format)
     }
 
-    /// Downloads an archive to a temporary directory and opens the archive.
-    ///
-    /// This function is only available if the `download` feature is enabled.
+    /// Opens an archive stored on the filesystem, like [`open`](Self::open),
+    /// and reports the progress of the indexing of zip archives, i.e. of the
+    /// reading of their central directory, which takes a noticeable time for
+    /// archives containing a large number of entries.
     ///
-    /// This function is a simple convenience wrapper around the [`Downloader`](crate::Downloader),
-    /// which provides more features.
+    /// The callback is called with the number of bytes of the central
+    /// directory read so far and its total size. The central directory is
+    /// read again by every operation on the archive, so the progress is
+    /// reported every time. It is never called for the other formats, which
+    /// have no central directory.
     ///
     /// # Arguments:
     ///
-    /// - `url`: the url to the archive file to open
+    /// - `path`: the path to the archive file to open
+    /// - `on_progress`: closure called with the number of bytes read and the total
     ///
     /// # Examples:
     ///
     /// ```no_run
-    /// use arkiv::Archive;
+    /// use arkiv::{Archive, Result};
     ///
-    /// let url = "https://github.com/meuter/arkiv-rs/raw/main/tests/sample/sample.zip";
-    /// let archive = Archive::download(url);
+    /// fn main() -> Result<()> {
+    ///     let mut archive = Archive::open_with_index_progress("path/to/large.zip", |read, total| {
+    ///         println!("indexing: {read}/{total} bytes");
+    ///     })?;
+    ///     println!("{} entries", archive.entry_count()?);
+    ///     Ok(())
+    /// }
     /// ```
     ///
-    #[cfg(feature = "download")]
-    pub fn download(url: impl AsRef<str>) -> Result<Self> {
-        crate::Downloader::new().url(url).to_temp().download()
-    }
-
-    fn archived(&mut self) -> Result<&mut Box<dyn Archived>> {
-        #[allow(unused)]
-        let file = File::open(self.path())?;
-
-        let result: Result<Box<dyn Archived>> = match self.format {
-            #[cfg(feature = "zip")]
-            Format::Zip => Ok(Box::new(Zip::new(file)?)),
-
-            #[cfg(feature = "tar")]
-            Format::Tar => Ok(Box::new(Tar::new(file))),
-
-            #[cfg(all(feature = "tar", feature = "gzip"))]
-            Format::TarGzip => Ok(Box::new(Tar::new(GzDecoder::new(file)))),
-
-            #[cfg(all(feature = "tar", feature = "bzip2"))]
-            Format::TarBzip2 => Ok(Box::new(Tar::new(BzDecoder::new(file)))),
-
-            #[cfg(all(feature = "tar", feature = "xz2"))]
-            Format::TarXz2 => Ok(Box::new(Tar::new(XzDecoder::new(file)))),
-
-            #[cfg(all(feature = "tar", feature = "zstd"))]
-            Format::TarZstd => Ok(Box::new(Tar::new(ZstdDecoder::new(file)?))),
-
-            _ => Err(Error::UnsupportedArchive(
-                "unsupported format, did you enable the proper feature?",
-            )),
-        };
-
-        self.archived.replace(result?);
-        Ok(self
-            .archived
-            .as_mut()
-            .expect("inner was freshly replaced, this should never happen"))
+    #[cfg(feature = "zip")]
+    pub fn open_with_index_progress(
+        path: impl AsRef<Path>,
+        on_progress: impl Fn(u64, u64) + 'static,
+    ) -> Result<Self> {
+        let mut archive = Archive::open(path)?;
+        archive.index_progress = Some(Rc::new(on_progress));
+        Ok(archive)
     }
 
-    /// Returns the format of the archive.
-    pub fn format(&self) -> &Format {
-        &self.format
-    }
-
-    /// Returns the path of the archive.
-    pub fn path(&self) -> Cow<Path> {
-        self.storage.as_path()
-    }
-
-    /// Returns the list of entries stored within the archive.
+    /// Opens an archive stored on the filesystem, like [`open`](Self::open),
+    /// but falls back to the magic bytes at the start of the file when its
+    /// extension does not correspond to any known format. See
+    /// [`infer_from_magic_bytes`](Format::infer_from_magic_bytes).
     ///
-    /// # Warning
+    /// Returns [`Error::ArchiveNotFound`] if the file does not exist,
+    /// [`Error::UnrecognizedFormat`] if neither its extension nor its
+    /// contents correspond to any known format, and
+    /// [`Error::UnsupportedArchive`] if the format is known but not supported
+    /// by the current build.
     ///
-    /// For convenience, these entries are returned as an already
-    /// collected `Vec<String>`. If the archive contains a large
-    /// number of files, the amount of memory required to store
-    /// these entries might be large. See [`entries_iter`](Self::entries_iter)
-    /// for an iterator version.
+    /// # Arguments:
     ///
-    /// # Example
+    /// - `path`: the path to the archive file to open
     ///
-    /// ```no_run
-    /// use arkiv::{Archive, Result};
+    /// # Examples:
     ///
-    /// fn main() -> Result<()> {
-    ///     let mut archive = Archive::open("path/to/archive.tgz")?;
-    ///     let entries: Vec<String> = archive.entries()?;
+    /// ```
+    /// use arkiv::Archive;
     ///
-    ///     for entry in entries {
-    ///         print!("{entry}");
-    ///     }
-    ///     Ok(())
-    /// }
+    /// let archive = Archive::open_with_magic_detection("path/to/download");
     /// ```
-    pub fn entries(&mut self) -> Result<Vec<String>> {
-        let mut result: Vec<String> = vec![];
-        for entry in self.entries_iter()? {
-            let entry = entry?;
-            result.push(entry.path().display().to_string());
+    ///
+    pub fn open_with_magic_detection(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        if !path.exists() {
+            Err(Error::ArchiveNotFound(path.to_path_buf()))?;
         }
-        Ok(result)
+        let mut format = Format::infer_from_file_extension(path);
+        if format == Format::Unknown {
+            let mut magic = vec![];
+            File::open(path)?
+                .take(MAGIC_BYTES_LEN as u64)
+                .read_to_end(&mut magic)?;
+            format = Format::infer_from_magic_bytes(&magic);
+        }
+        if format == Format::Unknown {
+            Err(Error::UnrecognizedFormat(path.to_path_buf()))?;
+        }
+        Archive::open_with_format(path, format)
     }
 
-    /// Constructs an iterator over the entries in this archive
+    /// Opens an archive stored on the filesystem with a given format,
+    /// regardless of its file extension (e.g. a temporary file without
+    /// extension).
     ///
-    /// # Example
+    /// Returns [`Error::ArchiveNotFound`] if the file does not exist, and
+    /// [`Error::UnsupportedArchive`] if the format is not supported by the
+    /// current build.
     ///
-    /// ```no_run
-    /// use arkiv::{Archive, Result};
+    /// # Arguments:
     ///
-    /// fn main() -> Result<()> {
-    ///     let mut archive = Archive::open("path/to/archive.tgz")?;
+    /// - `path`: the path to the archive file to open
+    /// - `format`: the format of the archive
+    ///
+    /// # Examples:
     ///
-    ///     for entry in archive.entries_iter()? {
-    ///         let entry = entry?;
-    ///         print!("{}", entry.path().display());
-    ///     }
-    ///     Ok(())
-    /// }
     /// ```
+    /// use arkiv::{Archive, Format};
     ///
+    /// let archive = Archive::open_with_format("path/to/archive", Format::TarGzip);
+    /// ```
     ///
-    pub fn entries_iter(&mut self) -> Result<Entries> {
-        self.archived()?.entries()
+    pub fn open_with_format(path: impl AsRef<Path>, format: Format) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        if !path.exists() {
+            Err(Error::ArchiveNotFound(path.clone()))?;
+        }
+        let storage = Storage::FileOnDisk { path };
+        Archive::with_format(storage, format)
     }
 
-    /// Unpacks the contents of the archive. On unix systems all permissions
-    /// will be preserved.
+    /// Decompresses a single compressed file (e.g. `data.bin.zst`) to the
+    /// given destination file, which is overwritten if it already exists.
+    /// Returns the number of decompressed bytes written.
     ///
-    /// # Arguments
+    /// Returns [`Error::UnsupportedArchive`] if `src` is an archive rather
+    /// than a single compressed file, see [`Format::is_archive`].
     ///
-    /// - `dest`: the destination folder (will be created if necessary)
+    /// # Arguments:
     ///
-    /// # Example
+    /// - `src`: the path of the compressed file
+    /// - `dest`: the path of the decompressed file to create
+    ///
+    /// # Examples:
     ///
     /// ```no_run
     /// use arkiv::{Archive, Result};
-    //
+    ///
     /// fn main() -> Result<()> {
-    ///    let mut archive = Archive::open("path/to/archive.tgz")?;
-    ///    archive.unpack("/tmp/extracted/")?;
-    ///    Ok(())
+    ///     let size = Archive::decompress("path/to/data.bin.zst", "/tmp/data.bin")?;
+    ///     println!("{size} bytes");
+    ///     Ok(())
     /// }
     /// ```
-    pub fn unpack(&mut self, dest: impl AsRef<Path>) -> Result<()> {
-        self.archived()?.unpack(dest.as_ref())
+    ///
+    pub fn decompress(src: impl AsRef<Path>, dest: impl AsRef<Path>) -> Result<u64> {
+        let src = src.as_ref();
+        let mut archive = Archive::open(src)?;
+        if archive.format.is_archive() {
+            Err(
+                Error::unsupported_archive("not a single compressed file, use unpack instead")
+                    .with_path(src),
+            )?;
+        }
+        let entry = archive
+            .entries_iter()?
+            .next()
+            .unwrap_or(Err(Error::FileNotFound))?;
+        let mut outfile = File::create(dest)?;
+        archive.unpack_entry_to_writer(&entry, &mut outfile)
     }
 
-    /// Returns an entry corresponding to a given path within the archive
+    /// Opens an archive stored in memory, e.g. received over a custom
+    /// transport or generated programmatically.
+    ///
+    /// Since there is no file extension to infer the format from, the
+    /// format of the archive must be provided. The data is not copied if
+    /// it is passed as a `Vec<u8>`.
+    ///
+    /// Returns [`Error::UnsupportedArchive`] if the format is not supported
+    /// by the current build.
+    ///
+    /// # Arguments:
+    ///
+    /// - `data`: the contents of the archive
+    /// - `format`: the format of the archive
+    ///
+    /// # Examples:
+    ///
+    /// ```no_run
+    /// use arkiv::{Archive, Format, Result};
+    ///
+    /// fn main() -> Result<()> {
+    ///     let data = std::fs::read("path/to/archive.zip")?;
+    ///     let mut archive = Archive::from_bytes(data, Format::Zip)?;
+    ///     archive.unpack("/tmp/extracted/")?;
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    pub fn from_bytes(data: impl Into<Vec<u8>>, format: Format) -> Result<Self> {
+        let data = Arc::from(data.into());
+        let storage = Storage::InMemory { data };
+        Archive::with_format(storage, format)
+    }
+
+    /// Opens an archive from an arbitrary reader, e.g. a memory-mapped
+    /// region or a file of a custom virtual filesystem.
+    ///
+    /// Since there is no file extension to infer the format from, the
+    /// format of the archive must be provided. The reader is rewound
+    /// every time the archive is read.
+    ///
+    /// Returns [`Error::UnsupportedArchive`] if the format is not supported
+    /// by the current build.
+    ///
+    /// # Arguments:
+    ///
+    /// - `reader`: the reader over the contents of the archive
+    /// - `format`: the format of the archive
+    ///
+    /// # Examples:
+    ///
+    /// ```no_run
+    /// use arkiv::{Archive, Format, Result};
+    /// use std::fs::File;
+    ///
+    /// fn main() -> Result<()> {
+    ///     let file = File::open("path/to/archive")?;
+    ///     let mut archive = Archive::from_reader(file, Format::TarGzip)?;
+    ///     archive.unpack("/tmp/extracted/")?;
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    pub fn from_reader<R: Read + Seek + Send + 'static>(reader: R, format: Format) -> Result<Self> {
+        let reader = SharedReader {
+            inner: Arc::new(Mutex::new(reader)),
+            pos: 0,
+        };
+        let storage = Storage::Reader { reader };
+        Archive::with_format(storage, format)
+    }
+
+    /// Opens all the archives whose path matches a glob pattern.
+    ///
+    /// This function is only available if the `glob` feature is enabled.
+    ///
+    /// The format of each archive is inferred from its file extension.
+    /// If no file matches the pattern, an empty `Vec` is returned.
+    ///
+    /// # Arguments:
+    ///
+    /// - `pattern`: a glob pattern, e.g. `dist/tool-*.tar.gz`
+    ///
+    /// # Examples:
+    ///
+    /// ```no_run
+    /// use arkiv::{Archive, Result};
+    ///
+    /// fn main() -> Result<()> {
+    ///     for mut archive in Archive::open_glob("dist/tool-*.tar.gz")? {
+    ///         archive.unpack("/tmp/extracted/")?;
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    #[cfg(feature = "glob")]
+    pub fn open_glob(pattern: &str) -> Result<Vec<Self>> {
+        glob::glob(pattern)
+            .map_err(|err| Error::InvalidPattern(err.to_string()))?
+            .map(|path| Archive::open(path.map_err(io::Error::from)?))
+            .collect()
+    }
+
+    /// Downloads an archive to a temporary directory and opens the archive.
+    ///
+    /// This function is only available if the `download` feature is enabled.
+    ///
+    /// This function is a simple convenience wrapper around the [`Downloader`](crate::Downloader),
+    /// which provides more features.
+    ///
+    /// # Arguments:
+    ///
+    /// - `url`: the url to the archive file to open
+    ///
+    /// # Examples:
+    ///
+    /// ```no_run
+    /// use arkiv::Archive;
+    ///
+    /// let url = "https://github.com/meuter/arkiv-rs/raw/main/tests/sample/sample.zip";
+    /// let archive = Archive::download(url);
+    /// ```
+    ///
+    #[cfg(feature = "download")]
+    pub fn download(url: impl AsRef<str>) -> Result<Self> {
+        crate::Downloader::new().url(url).to_temp().download()
+    }
+
+    /// Opens a tar based archive (e.g. `tar.gz`) by streaming it over HTTP,
+    /// without downloading it to the filesystem first.
+    ///
+    /// This function is only available if the `download` feature is enabled.
+    ///
+    /// Since the contents of the archive are read as they are received,
+    /// every operation on the returned archive (e.g. listing its entries,
+    /// then unpacking it) starts a new request. Other formats such as zip
+    /// require random access to the archive and cannot be streamed, see
+    /// [`Archive::download`] instead. The lenient mode has no effect on
+    /// streamed archives.
+    ///
+    /// Returns [`Error::UnsupportedArchive`] if the format is not a tar
+    /// based format supported by the current build.
+    ///
+    /// # Arguments:
+    ///
+    /// - `url`: the url to the archive file to open
+    /// - `format`: the format of the archive
+    ///
+    /// # Examples:
+    ///
+    /// ```no_run
+    /// use arkiv::{Archive, Format, Result};
+    ///
+    /// fn main() -> Result<()> {
+    ///     let url = "https://github.com/meuter/arkiv-rs/raw/main/tests/sample/sample.tar.gz";
+    ///     let mut archive = Archive::open_stream(url, Format::TarGzip)?;
+    ///     archive.unpack("/tmp/extracted/")?;
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    #[cfg(feature = "download")]
+    pub fn open_stream(url: &str, format: Format) -> Result<Self> {
+        if !format.is_tar() || !format.is_supported() {
+            Err(
+                Error::unsupported_archive("only tar based archives can be streamed")
+                    .with_path(Path::new(url)),
+            )?;
+        }
+        let storage = Storage::Stream {
+            url: url.to_string(),
+        };
+        Archive::with_format(storage, format)
+    }
+
+    /// Enables or disables the lenient mode (disabled by default).
+    ///
+    /// In lenient mode, known junk prefixes that some buggy tools prepend
+    /// to their output (e.g. a UTF-8 byte order mark in front of a gzip
+    /// stream) are skipped before the archive is decoded. In strict mode,
+    /// such archives are rejected as invalid.
+    ///
+    /// # Arguments
+    ///
+    /// - `lenient`: `true` to enable the lenient mode
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arkiv::{Archive, Result};
+    ///
+    /// fn main() -> Result<()> {
+    ///     let mut archive = Archive::open("path/to/archive.tgz")?.lenient(true);
+    ///     archive.unpack("/tmp/extracted/")?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn lenient(mut self, lenient: bool) -> Self {
+        self.options.lenient = lenient;
+        self
+    }
+
+    /// Sets the options used to read and unpack the archive.
+    ///
+    /// # Arguments
+    ///
+    /// - `options`: the options, see [`ArchiveOptions`]
+    pub fn with_options(mut self, options: ArchiveOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Returns the options used to read and unpack the archive.
+    pub fn options(&self) -> &ArchiveOptions {
+        &self.options
+    }
+
+    pub(crate) fn archived(&mut self) -> Result<&mut Box<dyn Archived>> {
+        let path = self.storage.as_path().into_owned();
+        let inner = self.open_archived().map_err(|err| err.with_path(&path))?;
+        Ok(self.archived.insert(Box::new(WithPath { inner, path })))
+    }
+
+    /// creates the backend matching the format of the archive
+    fn open_archived(&mut self) -> Result<Box<dyn Archived>> {
+        #[cfg(feature = "download")]
+        if let Storage::Stream { url } = &self.storage {
+            // the archive is requested again for every operation
+            return tar_archived(&self.format, crate::download::stream(url)?, &self.options);
+        }
+
+        #[allow(unused)]
+        let mut file = self.storage.open()?;
+        #[allow(unused)]
+        let path = self.storage.as_path().into_owned();
+        if self.options.lenient {
+            skip_junk_prefix(&mut file)?;
+        }
+        if self.options.validate_format {
+            check_magic_bytes(&mut file, &self.format)?;
+        }
+
+        match self.format {
+            #[cfg(feature = "zip")]
+            Format::Zip => self.zip_archived(file),
+
+            #[cfg(feature = "crx")]
+            Format::Crx => self.zip_archived(Box::new(check_crx_header(file)?)),
+
+            #[cfg(feature = "deb")]
+            Format::Deb => Ok(Box::new(Ar::new(file)?)),
+
+            _ if self.format.is_tar() => tar_archived(&self.format, file, &self.options),
+
+            #[cfg(feature = "sevenz")]
+            Format::SevenZ => Ok(Box::new(SevenZ::new(file)?)),
+
+            #[cfg(feature = "rpm")]
+            Format::Rpm => Ok(Box::new(Cpio::new(rpm::payload(file)?))),
+
+            #[cfg(feature = "gzip")]
+            Format::Gzip => Ok(Box::new(Compressed::new(GzDecoder::new(file), &path))),
+
+            #[cfg(feature = "bzip2")]
+            Format::Bzip2 => Ok(Box::new(Compressed::new(BzDecoder::new(file), &path))),
+
+            #[cfg(feature = "xz")]
+            Format::Xz2 => Ok(Box::new(Compressed::new(XzDecoder::new(file), &path))),
+
+            #[cfg(feature = "zstd")]
+            Format::Zstd => Ok(Box::new(Compressed::new(
+                zstd_decoder(file, &self.options)?,
+                &path,
+            ))),
+
+            _ => Err(Error::unsupported_archive(
+                "unsupported format, did you enable the proper feature?",
+            )),
+        }
+    }
+
+    /// creates the backend of a zip based archive, reporting the progress
+    /// of its indexing if requested
+    #[cfg(feature = "zip")]
+    fn zip_archived(&mut self, mut file: Box<dyn ReadSeek>) -> Result<Box<dyn Archived>> {
+        // the backend is created for every operation, the central directory
+        // is only parsed once
+        let creator_os = match &self.zip_creator_os {
+            Some(creator_os) => creator_os.clone(),
+            None => {
+                let creator_os = Rc::from(read_creator_os(&mut file)?);
+                self.zip_creator_os.insert(creator_os).clone()
+            }
+        };
+        match &self.index_progress {
+            Some(on_progress) => Ok(Box::new(Zip::with_index_progress(
+                file,
+                creator_os,
+                on_progress.clone(),
+            )?)),
+            None => Ok(Box::new(Zip::new(file, creator_os)?)),
+        }
+    }
+
+    /// Returns the format of the archive.
+    pub fn format(&self) -> &Format {
+        &self.format
+    }
+
+    /// Returns a human readable name of the format of the archive, meant to
+    /// be shown in user interfaces (e.g. `"ZIP"` or `"TAR+GZip"`).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arkiv::{Archive, Result};
+    ///
+    /// fn main() -> Result<()> {
+    ///     let archive = Archive::open("path/to/archive.tgz")?;
+    ///     println!("extracting {} archive...", archive.format_display());
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn format_display(&self) -> &'static str {
+        self.format.display_name()
+    }
+
+    /// Returns the path of the archive.
+    pub fn path(&self) -> Cow<Path> {
+        self.storage.as_path()
+    }
+
+    /// Returns the paths of the entries stored within the archive.
+    ///
+    /// # Warning
+    ///
+    /// For convenience, these entries are returned as an already
+    /// collected `Vec<String>`. If the archive contains a large
+    /// number of files, the amount of memory required to store
+    /// these entries might be large. See [`entries_iter`](Self::entries_iter)
+    /// for an iterator version.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arkiv::{Archive, Result};
+    ///
+    /// fn main() -> Result<()> {
+    ///     let mut archive = Archive::open("path/to/archive.tgz")?;
+    ///     let entries: Vec<String> = archive.entry_paths()?;
+    ///
+    ///     for entry in entries {
+    ///         print!("{entry}");
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn entry_paths(&mut self) -> Result<Vec<String>> {
+        let mut result: Vec<String> = vec![];
+        for entry in self.entries_iter()? {
+            let entry = entry?;
+            result.push(entry.path().display().to_string());
+        }
+        Ok(result)
+    }
+
+    /// Returns the paths of the entries stored within the archive.
+    #[deprecated(
+        since = "0.9.0",
+        note = "Archive::entries has been renamed to Archive::entry_paths, \
+                see also Archive::entries_sorted"
+    )]
+    pub fn entries(&mut self) -> Result<Vec<String>> {
+        self.entry_paths()
+    }
+
+    /// Returns the entries stored within the archive, sorted by path. The
+    /// order of the entries of an archive depends on how it was created, so
+    /// this is useful for a reproducible output (e.g. listings or diffs).
+    ///
+    /// # Warning
+    ///
+    /// All the entries are collected in memory before being sorted. See
+    /// [`entries_iter`](Self::entries_iter) for an iterator version.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arkiv::{Archive, Result};
+    ///
+    /// fn main() -> Result<()> {
+    ///     let mut archive = Archive::open("path/to/archive.tgz")?;
+    ///     for entry in archive.entries_sorted()? {
+    ///         println!("{} ({} bytes)", entry.path().display(), entry.size());
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn entries_sorted(&mut self) -> Result<Vec<Entry>> {
+        let mut entries = self.entries_iter()?.collect::<Result<Vec<Entry>>>()?;
+        entries.sort_by(|a, b| a.path().cmp(b.path()));
+        Ok(entries)
+    }
+
+    /// Constructs an iterator over the entries in this archive
+    ///
+    /// The iterator borrows the archive mutably until it is dropped. This
+    /// is required for tar archives: the entries of a tar archive can only
+    /// be read by streaming through the underlying reader, which is owned
+    /// by the archive and cannot be moved into the iterator since the `tar`
+    /// crate only hands out iterators borrowing it. To process the entries
+    /// without holding on to the archive, see
+    /// [`into_entry_channel`](Self::into_entry_channel), or collect the
+    /// entries first.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arkiv::{Archive, Result};
+    ///
+    /// fn main() -> Result<()> {
+    ///     let mut archive = Archive::open("path/to/archive.tgz")?;
+    ///
+    ///     for entry in archive.entries_iter()? {
+    ///         let entry = entry?;
+    ///         print!("{}", entry.path().display());
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    ///
+    pub fn entries_iter(&mut self) -> Result<Entries> {
+        self.archived()?.entries()
+    }
+
+    /// Returns a cursor over the entries in this archive, which can be
+    /// moved back and forth without collecting all the entries.
+    ///
+    /// See [`EntryCursor`] for the cost of moving the cursor depending on
+    /// the format of the archive.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arkiv::{Archive, Result};
+    /// use std::io::read_to_string;
+    ///
+    /// fn main() -> Result<()> {
+    ///     let mut archive = Archive::open("path/to/archive.zip")?;
+    ///     let mut cursor = archive.cursor()?;
+    ///
+    ///     if let Some(entry) = cursor.seek_to(42)? {
+    ///         println!("{}", entry.path().display());
+    ///         println!("{}", read_to_string(cursor.read_current()?)?);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn cursor(&mut self) -> Result<EntryCursor> {
+        Ok(EntryCursor {
+            archive: self,
+            index: None,
+            current: None,
+        })
+    }
+
+    /// Returns the number of entries in the archive if it can be known
+    /// without reading the whole archive, e.g. for zip archives where it is
+    /// stored in the central directory. Returns `None` for tar archives,
+    /// which would have to be scanned entirely.
+    ///
+    /// This can be used to e.g. show a progress bar before unpacking.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arkiv::{Archive, Result};
+    ///
+    /// fn main() -> Result<()> {
+    ///     let mut archive = Archive::open("path/to/archive.zip")?;
+    ///     if let Some(count) = archive.entry_count_hint()? {
+    ///         println!("extracting {count} entries");
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn entry_count_hint(&mut self) -> Result<Option<usize>> {
+        Ok(self.archived()?.entry_count_hint())
+    }
+
+    /// Returns the number of entries in the archive, without collecting
+    /// them. The count is read from the central directory of zip archives,
+    /// the other archives are iterated over.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arkiv::{Archive, Result};
+    ///
+    /// fn main() -> Result<()> {
+    ///     let mut archive = Archive::open("path/to/archive.tgz")?;
+    ///     println!("{} entries", archive.entry_count()?);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn entry_count(&mut self) -> Result<usize> {
+        match self.entry_count_hint()? {
+            Some(count) => Ok(count),
+            None => self.count_entries(|_| true),
+        }
+    }
+
+    /// Returns the number of regular files in the archive, without
+    /// collecting its entries.
+    pub fn file_count(&mut self) -> Result<usize> {
+        self.count_entries(Entry::is_file)
+    }
+
+    /// Returns the number of directories in the archive, without collecting
+    /// its entries. Directories that are only implied by the path of a file
+    /// are not counted.
+    pub fn dir_count(&mut self) -> Result<usize> {
+        self.count_entries(Entry::is_dir)
+    }
+
+    /// Returns the sum of the uncompressed sizes of the entries of the
+    /// archive, e.g. to check that there is enough disk space before
+    /// unpacking it. See also [`scan`](Self::scan).
+    pub fn total_uncompressed_size(&mut self) -> Result<u64> {
+        let mut total = 0;
+        for entry in self.entries_iter()? {
+            total += entry?.size();
+        }
+        Ok(total)
+    }
+
+    /// Returns `true` if the archive contains no entry. Only the first
+    /// entry is read, unless the number of entries is already known (e.g.
+    /// for zip archives).
+    pub fn is_empty(&mut self) -> Result<bool> {
+        match self.entry_count_hint()? {
+            Some(count) => Ok(count == 0),
+            None => Ok(self.entries_iter()?.next().transpose()?.is_none()),
+        }
+    }
+
+    fn count_entries(&mut self, mut predicate: impl FnMut(&Entry) -> bool) -> Result<usize> {
+        let mut count = 0;
+        for entry in self.entries_iter()? {
+            if predicate(&entry?) {
+                count += 1;
+            }
+        }
+        Ok(count)
+    }
+
+    /// Returns, for each directory in the archive, the total uncompressed
+    /// size of all the files it contains, recursively. Directories that are
+    /// not explicitly stored in the archive but are implied by the path of
+    /// a file are also reported.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arkiv::{Archive, Result};
+    ///
+    /// fn main() -> Result<()> {
+    ///     let mut archive = Archive::open("path/to/archive.tgz")?;
+    ///
+    ///     for (dir, size) in archive.directory_sizes()? {
+    ///         println!("{size}\t{}", dir.display());
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn directory_sizes(&mut self) -> Result<BTreeMap<PathBuf, u64>> {
+        let mut sizes: BTreeMap<PathBuf, u64> = BTreeMap::new();
+        for entry in self.entries_iter()? {
+            let entry = entry?;
+            let path: PathBuf = entry.path().components().collect();
+            if entry.is_dir() {
+                sizes.entry(path).or_default();
+            } else if entry.is_file() {
+                for dir in path.ancestors().skip(1) {
+                    if dir.as_os_str().is_empty() {
+                        break;
+                    }
+                    *sizes.entry(dir.to_path_buf()).or_default() += entry.size();
+                }
+            }
+        }
+        Ok(sizes)
+    }
+
+    /// Counts the entries of the archive and gathers their sizes and depths
+    /// in a single pass, which is cheaper than calling e.g.
+    /// [`entries_iter`](Self::entries_iter) several times on tar archives.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arkiv::{Archive, Result};
+    ///
+    /// fn main() -> Result<()> {
+    ///     let mut archive = Archive::open("path/to/archive.tgz")?;
+    ///     let scan = archive.scan()?;
+    ///     println!(
+    ///         "{} files, {} directories, {} bytes",
+    ///         scan.file_count, scan.dir_count, scan.total_size
+    ///     );
+    ///     if let Some(largest) = scan.largest {
+    ///         println!("largest: {}", largest.path().display());
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn scan(&mut self) -> Result<ArchiveScan> {
+        let mut scan = ArchiveScan::default();
+        for entry in self.entries_iter()? {
+            scan.add(entry?);
+        }
+        Ok(scan)
+    }
+
+    /// Checks that the entries of the archive are exactly the expected ones.
+    ///
+    /// Returns [`Error::ContentMismatch`] listing the expected entries that
+    /// are missing from the archive and the entries of the archive that were
+    /// not expected. Paths are compared component-wise so `dir/` and `dir` are
+    /// considered equal.
+    ///
+    /// # Arguments
+    ///
+    /// - `expected`: the paths of all the entries expected in the archive
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arkiv::{Archive, Result};
+    /// use std::path::Path;
+    ///
+    /// fn main() -> Result<()> {
+    ///     let mut archive = Archive::open("path/to/archive.tgz")?;
+    ///     archive.assert_contains_exactly(&[
+    ///         Path::new("bin/"),
+    ///         Path::new("bin/tool"),
+    ///     ])?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn assert_contains_exactly(&mut self, expected: &[&Path]) -> Result<()> {
+        let mut missing: BTreeSet<PathBuf> = expected.iter().map(|p| p.to_path_buf()).collect();
+        let mut unexpected: Vec<PathBuf> = vec![];
+        for entry in self.entries_iter()? {
+            let entry = entry?;
+            if !missing.remove(entry.path()) {
+                unexpected.push(entry.path().to_path_buf());
+            }
+        }
+
+        if missing.is_empty() && unexpected.is_empty() {
+            Ok(())
+        } else {
+            let missing = missing.into_iter().collect();
+            unexpected.sort();
+            Err(Error::ContentMismatch {
+                missing,
+                unexpected,
+            })
+        }
+    }
+
+    /// Unpacks the contents of the archive. On unix systems all permissions
+    /// will be preserved, and files whose extension is listed in
+    /// [`ArchiveOptions::executable_extensions`] will be made executable.
+    ///
+    /// Symbolic and hard links are recreated, while the other special
+    /// entries (e.g. devices or FIFOs) are skipped, unless
+    /// [`ArchiveOptions::strict_entry_types`] is set.
+    ///
+    /// # Arguments
+    ///
+    /// - `dest`: the destination folder (will be created if necessary)
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arkiv::{Archive, Result};
+    //
+    /// fn main() -> Result<()> {
+    ///    let mut archive = Archive::open("path/to/archive.tgz")?;
+    ///    archive.unpack("/tmp/extracted/")?;
+    ///    Ok(())
+    /// }
+    /// ```
+    pub fn unpack(&mut self, dest: impl AsRef<Path>) -> Result<()> {
+        self.unpack_and_notify(dest.as_ref(), &mut || {})
+    }
+
+    /// Unpacks the contents of the archive like [`unpack`](Self::unpack),
+    /// calling `on_progress(entries_done, entries_total)` once before
+    /// unpacking the first entry and then every time an entry (file,
+    /// directory, link, ...) has been unpacked, e.g. to draw a progress bar.
+    ///
+    /// Progress is measured in entries rather than in bytes, since the
+    /// uncompressed size of single compressed files is not known in advance.
+    /// The total number of entries is read from the central directory of zip
+    /// and 7z archives, but requires an additional pass over tar archives,
+    /// which are thus decompressed (or downloaded, when streamed) twice. The
+    /// directories of tar archives are unpacked after the files they contain.
+    ///
+    /// # Arguments
+    ///
+    /// - `dest`: the destination folder (will be created if necessary)
+    /// - `on_progress`: the callback receiving the number of entries unpacked
+    ///   so far and the total number of entries
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arkiv::{Archive, Result};
+    ///
+    /// fn main() -> Result<()> {
+    ///     let mut archive = Archive::open("path/to/archive.tgz")?;
+    ///     archive.unpack_with_progress("/tmp/extracted/", |done, total| {
+    ///         println!("{done}/{total}");
+    ///     })?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn unpack_with_progress(
+        &mut self,
+        dest: impl AsRef<Path>,
+        mut on_progress: impl FnMut(u64, u64),
+    ) -> Result<()> {
+        let total = self.entry_count()? as u64;
+        let mut done = 0;
+        on_progress(done, total);
+        self.unpack_and_notify(dest.as_ref(), &mut || {
+            done += 1;
+            on_progress(done, total);
+        })
+    }
+
+    fn unpack_and_notify(&mut self, dest: &Path, on_unpacked: &mut OnUnpacked) -> Result<()> {
+        if self.options.strict_entry_types {
+            // the entries that would be skipped are reported before anything is unpacked
+            if let Some(entry) = self.find(Entry::is_other)?.next() {
+                Err(Error::EntryTypeNotSupported(entry?.entry_type))?;
+            }
+        }
+        self.archived()?.unpack(dest, on_unpacked)?;
+        if !self.options.executable_extensions.is_empty() {
+            let entries = self.entries_iter()?.collect::<Result<Vec<_>>>()?;
+            for entry in entries {
+                self.options.apply_executable_extensions(&entry, dest)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Unpacks the contents of the archive in a reproducible way: entries
+    /// are extracted in sorted path order, and the modification time of every
+    /// extracted file, symlink and directory is set to `fixed_time`, regardless
+    /// of the times stored in the archive. Unpacking the same archive twice
+    /// thus produces identical trees.
+    ///
+    /// Hard links are extracted after the other entries, once the entries
+    /// they point to exist.
+    ///
+    /// # Arguments
+    ///
+    /// - `dest`: the destination folder (will be created if necessary)
+    /// - `fixed_time`: the modification time given to every extracted entry
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arkiv::{Archive, Result};
+    /// use std::time::SystemTime;
+    //
+    /// fn main() -> Result<()> {
+    ///    let mut archive = Archive::open("path/to/archive.tgz")?;
+    ///    archive.unpack_reproducible("/tmp/extracted/", SystemTime::UNIX_EPOCH)?;
+    ///    Ok(())
+    /// }
+    /// ```
+    pub fn unpack_reproducible(
+        &mut self,
+        dest: impl AsRef<Path>,
+        fixed_time: SystemTime,
+    ) -> Result<()> {
+        let dest = dest.as_ref();
+        let mut entries = self.entries_iter()?.collect::<Result<Vec<_>>>()?;
+        if self.options.strict_entry_types {
+            // the entries that would be skipped are reported before anything is unpacked
+            if let Some(entry) = entries.iter().find(|entry| entry.is_other()) {
+                Err(Error::EntryTypeNotSupported(entry.entry_type.clone()))?;
+            }
+        }
+        entries.retain(|entry| !entry.is_other());
+        entries.sort_by(|a, b| (a.is_hardlink(), a.path()).cmp(&(b.is_hardlink(), b.path())));
+
+        let fixed_time = FileTime::from_system_time(fixed_time);
+        let mut directories = BTreeSet::new();
+        for entry in &entries {
+            self.unpack_entry(entry, dest)?;
+            let path = unpack_path(entry.path());
+            directories.extend(
+                path.ancestors()
+                    .skip(1)
+                    .filter(|p| !p.as_os_str().is_empty()),
+            );
+            if entry.is_dir() {
+                directories.insert(path);
+            } else {
+                set_fixed_time(dest, path, fixed_time)?;
+            }
+        }
+
+        // unpacking the contents of a directory changes its modification
+        // time, so the directories are only handled once everything is unpacked
+        for path in directories {
+            set_fixed_time(dest, path, fixed_time)?;
+        }
+        Ok(())
+    }
+
+    /// Returns an entry corresponding to a given path within the archive
+    ///
+    /// # Arguments
+    ///
+    /// - `entry_path`: the path of the enty to look up
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arkiv::{Archive, Result};
+    //
+    /// fn main() -> Result<()> {
+    ///    let mut archive = Archive::open("path/to/archive.tgz")?;
+    ///    let entry = archive.entry_by_name("some/file_in_the_archive.txt")?;
+    ///    println!("{}", entry.path().display());
+    ///    Ok(())
+    /// }
+    /// ```
+    pub fn entry_by_name(&mut self, entry_path: impl AsRef<Path>) -> Result<Entry> {
+        self.find(|entry| entry.path() == entry_path.as_ref())?
+            .next()
+            .unwrap_or(Err(Error::FileNotFound))
+    }
+
+    /// Returns an entry corresponding to a given path within the archive,
+    /// the paths being compared case-insensitively. This is useful for
+    /// archives created on Windows, in which the case of the paths is often
+    /// inconsistent. If several entries only differ by their case, the first
+    /// one in the archive is returned.
+    ///
+    /// # Arguments
+    ///
+    /// - `entry_path`: the path of the entry to look up, in any case
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arkiv::{Archive, Result};
+    ///
+    /// fn main() -> Result<()> {
+    ///     let mut archive = Archive::open("path/to/archive.zip")?;
+    ///     let entry = archive.entry_by_name_ci("readme.md")?;
+    ///     println!("{}", entry.path().display());
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn entry_by_name_ci(&mut self, entry_path: impl AsRef<Path>) -> Result<Entry> {
+        let lowercase = |path: &Path| path.to_string_lossy().to_lowercase();
+        let entry_path = lowercase(entry_path.as_ref());
+        self.find(|entry| lowercase(entry.path()) == entry_path)?
+            .next()
+            .unwrap_or(Err(Error::FileNotFound))
+    }
+
+    /// Returns `true` if the archive contains an entry at the given path.
+    /// The archive is only read until a matching entry is found, and zip
+    /// archives are looked up from their central directory without reading
+    /// the metadata of their entries.
+    ///
+    /// # Arguments
+    ///
+    /// - `entry_path`: the path of the entry to look up
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arkiv::{Archive, Result};
+    ///
+    /// fn main() -> Result<()> {
+    ///     let mut archive = Archive::open("path/to/archive.tgz")?;
+    ///     if archive.contains("project/Makefile")? {
+    ///         println!("found a Makefile");
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn contains(&mut self, entry_path: impl AsRef<Path>) -> Result<bool> {
+        self.archived()?.contains(entry_path.as_ref())
+    }
+
+    /// Returns the entry at a given index within the archive, as returned by
+    /// [`Entry::index`]. Unlike paths, indices are unique even when several
+    /// entries share the same path.
+    ///
+    /// Returns [`Error::FileNotFound`] if the archive contains fewer entries.
+    ///
+    /// # Arguments
+    ///
+    /// - `index`: the index of the entry to look up
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arkiv::{Archive, Result};
+    //
+    /// fn main() -> Result<()> {
+    ///    let mut archive = Archive::open("path/to/archive.tgz")?;
+    ///    let entry = archive.entry_by_index(0)?;
+    ///    println!("{}", entry.path().display());
+    ///    Ok(())
+    /// }
+    /// ```
+    pub fn entry_by_index(&mut self, index: usize) -> Result<Entry> {
+        // zip archives look the entry up directly in their central directory
+        self.entries_iter()?
+            .nth(index)
+            .unwrap_or(Err(Error::FileNotFound))
+    }
+
+    /// Returns an iterator over the entries in the archive
+    /// that match a given boolean predicate.
+    ///
+    /// # Arguments
+    ///
+    /// - `predicate`: a boolean predicate on `Entry`
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use arkiv::{Archive, Result};
+    ///
+    /// fn main() -> Result<()> {
+    ///     let mut archive = Archive::open("path/to/archive.tgz")?;
+    ///
+    ///     // iterate over all the files in the archive
+    ///     for entry in archive.find(|entry| entry.is_file())? {
+    ///         let entry = entry?;
+    ///         print!("{}", entry.path().display());
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn find<P: FnMut(&Entry) -> bool>(&mut self, predicate: P) -> Result<FindEntries<P>> {
+        Ok(FindEntries {
+            predicate,
+            inner: self.entries_iter()?,
+        })
+    }
+
+    /// Returns the first entry of the archive matching a given boolean
+    /// predicate, or `None` if there is none. The archive is only read until
+    /// a matching entry is found.
+    ///
+    /// # Arguments
+    ///
+    /// - `predicate`: a boolean predicate on `Entry`
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arkiv::{Archive, Result};
+    ///
+    /// fn main() -> Result<()> {
+    ///     let mut archive = Archive::open("path/to/archive.tgz")?;
+    ///     let manifest = archive.find_first(|entry| entry.path().ends_with("Cargo.toml"))?;
+    ///     if let Some(manifest) = manifest {
+    ///         println!("{}", manifest.path().display());
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn find_first<P: FnMut(&Entry) -> bool>(&mut self, predicate: P) -> Result<Option<Entry>> {
+        self.find(predicate)?.next().transpose()
+    }
+
+    /// Returns the last entry of the archive matching a given boolean
+    /// predicate, or `None` if there is none. The whole archive is read.
+    ///
+    /// # Arguments
+    ///
+    /// - `predicate`: a boolean predicate on `Entry`
+    pub fn find_last<P: FnMut(&Entry) -> bool>(&mut self, predicate: P) -> Result<Option<Entry>> {
+        let mut last = None;
+        for entry in self.find(predicate)? {
+            last = Some(entry?);
+        }
+        Ok(last)
+    }
+
+    /// Returns an iterator over the entries of the archive whose path
+    /// matches a glob pattern, e.g. `**/*.rs`. `?` matches any character,
+    /// `*` any sequence of characters within a path component and `**` any
+    /// sequence of path components. The trailing `/` of directories is
+    /// ignored.
+    ///
+    /// This function is only available if the `glob` feature is enabled.
+    ///
+    /// Returns [`Error::InvalidPattern`] if the pattern is invalid.
     ///
     /// # Arguments
     ///
-    /// - `entry_path`: the path of the enty to look up
+    /// - `pattern`: a glob pattern, e.g. `src/**/*.rs`
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arkiv::{Archive, Result};
+    ///
+    /// fn main() -> Result<()> {
+    ///     let mut archive = Archive::open("path/to/archive.tgz")?;
+    ///     for entry in archive.find_glob("**/*.rs")? {
+    ///         println!("{}", entry?.path().display());
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "glob")]
+    pub fn find_glob(&mut self, pattern: &str) -> Result<FindEntries<impl FnMut(&Entry) -> bool>> {
+        let pattern =
+            glob::Pattern::new(pattern).map_err(|err| Error::InvalidPattern(err.to_string()))?;
+        let options = glob::MatchOptions {
+            require_literal_separator: true,
+            ..glob::MatchOptions::new()
+        };
+        self.find(move |entry| {
+            let path: PathBuf = entry.path().components().collect();
+            pattern.matches_path_with(&path, options)
+        })
+    }
+
+    /// Returns an iterator over the regular files of the archive, i.e. a
+    /// shorthand for `archive.find(Entry::is_file)`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arkiv::{Archive, Result};
+    ///
+    /// fn main() -> Result<()> {
+    ///     let mut archive = Archive::open("path/to/archive.tgz")?;
+    ///     for file in archive.files()? {
+    ///         println!("{}", file?.path().display());
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn files(&mut self) -> Result<FindEntries<impl FnMut(&Entry) -> bool>> {
+        self.find(Entry::is_file)
+    }
+
+    /// Returns an iterator over the directories of the archive, i.e. a
+    /// shorthand for `archive.find(Entry::is_dir)`. Directories that are only
+    /// implied by the path of a file are not returned.
+    pub fn dirs(&mut self) -> Result<FindEntries<impl FnMut(&Entry) -> bool>> {
+        self.find(Entry::is_dir)
+    }
+
+    /// Returns an iterator over the entries of the archive having the given
+    /// extension, compared case-insensitively (e.g. `"txt"` also matches
+    /// `README.TXT`), i.e. a shorthand for a predicate on
+    /// [`Path::extension`] passed to [`Archive::find`].
     ///
     /// # Example
     ///
     /// ```no_run
     /// use arkiv::{Archive, Result};
-    //
+    ///
     /// fn main() -> Result<()> {
-    ///    let mut archive = Archive::open("path/to/archive.tgz")?;
-    ///    let entry = archive.entry_by_name("some/file_in_the_archive.txt")?;
-    ///    println!("{}", entry.path().display());
-    ///    Ok(())
+    ///     let mut archive = Archive::open("path/to/archive.tgz")?;
+    ///     for library in archive.find_by_extension("so")? {
+    ///         println!("{}", library?.path().display());
+    ///     }
+    ///     Ok(())
     /// }
     /// ```
-    pub fn entry_by_name(&mut self, entry_path: impl AsRef<Path>) -> Result<Entry> {
-        self.find(|entry| entry.path() == entry_path.as_ref())?
-            .next()
-            .unwrap_or(Err(Error::FileNotFound))
+    pub fn find_by_extension(
+        &mut self,
+        ext: impl AsRef<OsStr>,
+    ) -> Result<FindEntries<impl FnMut(&Entry) -> bool>> {
+        let ext = ext.as_ref().to_os_string();
+        self.find(move |entry| {
+            entry
+                .path()
+                .extension()
+                .is_some_and(|extension| extension.eq_ignore_ascii_case(&ext))
+        })
     }
 
-    /// Returns an iterator over the entries in the archive
-    /// that match a given boolean predicate.
+    /// Unpacks the entries of the archive that match a given boolean
+    /// predicate, in a single pass over the archive. The intermediate
+    /// directories of the matching entries are created as necessary, even
+    /// if they do not match themselves.
+    ///
+    /// Files, directories, symbolic links and hard links are unpacked, other
+    /// matching entries (e.g. devices) are skipped.
     ///
     /// # Arguments
     ///
+    /// - `dest`: the destination folder (will be created if necessary)
     /// - `predicate`: a boolean predicate on `Entry`
     ///
-    /// # Examples
+    /// # Example
     ///
     /// ```no_run
     /// use arkiv::{Archive, Result};
     ///
     /// fn main() -> Result<()> {
     ///     let mut archive = Archive::open("path/to/archive.tgz")?;
+    ///     archive.unpack_matching("/tmp/extracted/", |entry| {
+    ///         entry.path().starts_with("docs/")
+    ///     })?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn unpack_matching(
+        &mut self,
+        dest: impl AsRef<Path>,
+        mut predicate: impl FnMut(&Entry) -> bool,
+    ) -> Result<()> {
+        let dest = dest.as_ref();
+        create_dir_all(dest)?;
+        let options = self.options.clone();
+        self.archived()?.walk(&mut |entry, reader| {
+            if predicate(&entry) {
+                if entry.is_other() && options.strict_entry_types {
+                    Err(Error::EntryTypeNotSupported(entry.entry_type.clone()))?;
+                }
+                extract(&entry, reader, dest)?;
+                options.apply_executable_extensions(&entry, dest)?;
+            }
+            Ok(ControlFlow::Continue(()))
+        })
+    }
+
+    /// Unpacks the archive, stripping a leading path prefix from the
+    /// entries, e.g. the `project-1.2.3/` top-level directory commonly
+    /// found in release tarballs.
     ///
-    ///     // iterate over all the files in the archive
-    ///     for entry in archive.find(|entry| entry.is_file())? {
-    ///         let entry = entry?;
-    ///         print!("{}", entry.path().display());
-    ///     }
+    /// Entries located under `prefix` are unpacked relative to `dest` with
+    /// the prefix removed, the entry matching `prefix` itself is skipped.
+    /// Depending on `unmatched`, the entries that are not located under
+    /// `prefix` are either unpacked as-is or skipped.
+    ///
+    /// # Arguments
+    ///
+    /// - `dest`: the destination folder (will be created if necessary)
+    /// - `prefix`: the path prefix to strip from the entries
+    /// - `unmatched`: what to do with the entries not located under `prefix`
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::Io`] of kind [`io::ErrorKind::NotFound`] if no
+    /// entry of the archive is located under `prefix`. In this case, nothing
+    /// is unpacked.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arkiv::{Archive, Result, Unmatched};
+    ///
+    /// fn main() -> Result<()> {
+    ///     let mut archive = Archive::open("path/to/project-1.2.3.tar.gz")?;
+    ///     archive.unpack_with_strip_prefix("/tmp/project/", "project-1.2.3", Unmatched::Skip)?;
     ///     Ok(())
     /// }
     /// ```
-    pub fn find<P: FnMut(&Entry) -> bool>(&mut self, predicate: P) -> Result<FindEntries<P>> {
-        Ok(FindEntries {
-            predicate,
-            inner: self.entries_iter()?,
+    pub fn unpack_with_strip_prefix(
+        &mut self,
+        dest: impl AsRef<Path>,
+        prefix: impl AsRef<Path>,
+        unmatched: Unmatched,
+    ) -> Result<()> {
+        let (dest, prefix) = (dest.as_ref(), prefix.as_ref());
+        let mut matching = self.find(|entry| entry.path().starts_with(prefix))?;
+        let found = matching.next().transpose()?.is_some();
+        drop(matching);
+        if !found {
+            let message = format!("prefix not found in archive: '{}'", prefix.display());
+            Err(io::Error::new(io::ErrorKind::NotFound, message))?;
+        }
+        create_dir_all(dest)?;
+        let options = self.options.clone();
+        self.archived()?.walk(&mut |mut entry, reader| {
+            match entry.path.strip_prefix(prefix) {
+                Ok(path) if path.as_os_str().is_empty() => {
+                    return Ok(ControlFlow::Continue(()));
+                }
+                Ok(path) => entry.path = path.to_path_buf(),
+                Err(_) if unmatched == Unmatched::Skip => {
+                    return Ok(ControlFlow::Continue(()));
+                }
+                Err(_) => {}
+            }
+            // hard link targets are relative to the root of the archive
+            if let EntryType::Hardlink { target } = &mut entry.entry_type {
+                if let Ok(stripped) = target.strip_prefix(prefix) {
+                    *target = stripped.to_path_buf();
+                }
+            }
+            if entry.is_other() && options.strict_entry_types {
+                Err(Error::EntryTypeNotSupported(entry.entry_type.clone()))?;
+            }
+            extract(&entry, reader, dest)?;
+            options.apply_executable_extensions(&entry, dest)?;
+            Ok(ControlFlow::Continue(()))
         })
     }
 
@@ -355,6 +2033,378 @@ impl Archive {
     /// }
     /// ```
     pub fn unpack_entry(&mut self, entry: &Entry, dest: impl AsRef<Path>) -> Result<()> {
-        self.archived()?.unpack_entry(entry, dest.as_ref())
+        let dest = dest.as_ref();
+        self.archived()?.unpack_entry(entry, dest)?;
+        self.options.apply_executable_extensions(entry, dest)
+    }
+
+    /// Checks the integrity of a single entry of the archive.
+    ///
+    /// For zip archives, the contents of the entry are read and checked
+    /// against the CRC-32 stored in the archive. For tar archives, the
+    /// checksum of the header of the entry is checked.
+    ///
+    /// Returns `Ok(false)` if the checksum does not match, which indicates
+    /// that the entry is corrupted, and an error if the entry could not be
+    /// read at all.
+    ///
+    /// # Arguments
+    ///
+    /// - `entry`: the entry to check
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arkiv::{Archive, Result};
+    ///
+    /// fn main() -> Result<()> {
+    ///     let mut archive = Archive::open("path/to/archive.zip")?;
+    ///     let entry = archive.entry_by_name("some/file_in_the_archive.txt")?;
+    ///     if !archive.verify_entry(&entry)? {
+    ///         println!("{} is corrupted", entry.path().display());
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn verify_entry(&mut self, entry: &Entry) -> Result<bool> {
+        self.archived()?.verify_entry(entry)
+    }
+
+    /// Reads the archive in a background thread and sends each entry,
+    /// along with its decompressed contents, over a bounded channel.
+    ///
+    /// The channel holds at most `capacity` entries: once it is full,
+    /// the background thread blocks until the receiver catches up, which
+    /// provides natural backpressure and ensures at most `capacity` entries
+    /// are held in memory at any time.
+    ///
+    /// The background thread stops once all the entries have been sent, or
+    /// as soon as the [`Receiver`] is dropped. If an error occurs while
+    /// reading the archive, it is sent as the last item over the channel.
+    ///
+    /// # Arguments
+    ///
+    /// - `capacity`: the maximum number of entries buffered in the channel
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arkiv::{Archive, Result};
+    ///
+    /// fn main() -> Result<()> {
+    ///     let archive = Archive::open("path/to/archive.tgz")?;
+    ///
+    ///     for item in archive.into_entry_channel(4) {
+    ///         let (entry, data) = item?;
+    ///         println!("{} {}", entry.path().display(), data.len());
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn into_entry_channel(self, capacity: usize) -> Receiver<Result<(Entry, Vec<u8>)>> {
+        let (sender, receiver) = sync_channel(capacity);
+        let Archive {
+            format,
+            storage,
+            options,
+            ..
+        } = self;
+
+        thread::spawn(move || {
+            let mut archive = Archive {
+                format,
+                storage,
+                archived: None,
+                options,
+                #[cfg(feature = "zip")]
+                index_progress: None,
+                #[cfg(feature = "zip")]
+                zip_creator_os: None,
+            };
+            let result = archive.archived().and_then(|archived| {
+                archived.walk(&mut |entry, reader| {
+                    let mut data = vec![];
+                    reader.read_to_end(&mut data)?;
+                    match sender.send(Ok((entry, data))) {
+                        Ok(()) => Ok(ControlFlow::Continue(())),
+                        Err(_) => Ok(ControlFlow::Break(())),
+                    }
+                })
+            });
+            if let Err(err) = result {
+                let _ = sender.send(Err(err));
+            }
+        });
+
+        receiver
+    }
+
+    /// Extracts a single file from the archive to the provided destination
+    /// file and makes it executable (mode `0o755`). On non-unix systems the
+    /// permissions are left untouched.
+    ///
+    /// The parent directories of the destination file will be created as
+    /// necessary.
+    ///
+    /// # Arguments
+    ///
+    /// - `entry_path`: the path of the entry to extract
+    /// - `dest_file`: the path of the file to create
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arkiv::{Archive, Result};
+    ///
+    /// fn main() -> Result<()> {
+    ///     let mut archive = Archive::open("path/to/tool-x86_64-linux.tgz")?;
+    ///     let tool = archive.extract_executable("tool-x86_64-linux/tool", "/usr/local/bin/tool")?;
+    ///     println!("installed {}", tool.display());
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn extract_executable(
+        &mut self,
+        entry_path: impl AsRef<Path>,
+        dest_file: impl AsRef<Path>,
+    ) -> Result<PathBuf> {
+        let entry = self.entry_by_name(entry_path)?;
+        if !entry.is_file() {
+            Err(Error::EntryTypeNotSupported(entry.entry_type.clone()))?;
+        }
+
+        let dest_file = dest_file.as_ref();
+        if let Some(parent) = dest_file.parent() {
+            create_dir_all(parent)?;
+        }
+        let mut reader = self.archived()?.entry_reader(&entry)?;
+        let mut file = File::create(dest_file)?;
+        io::copy(&mut reader, &mut file)?;
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            file.set_permissions(std::fs::Permissions::from_mode(0o755))?;
+        }
+
+        Ok(dest_file.to_path_buf())
+    }
+
+    /// Opens a single file of the archive and returns a reader over its
+    /// decompressed contents, which are streamed from the archive as they
+    /// are read rather than loaded in memory.
+    ///
+    /// The archive is reopened at every call, so calling this function
+    /// twice returns a reader positioned at the start of the entry again.
+    ///
+    /// Returns [`Error::FileNotFound`] if the archive contains no entry at
+    /// this path, and [`Error::EntryTypeNotSupported`] if the entry is not
+    /// a regular file.
+    ///
+    /// # Arguments
+    ///
+    /// - `entry_path`: the path of the entry to open
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arkiv::{Archive, Result};
+    /// use std::{fs::File, io};
+    ///
+    /// fn main() -> Result<()> {
+    ///     let mut archive = Archive::open("path/to/archive.tgz")?;
+    ///     let mut reader = archive.open_entry("backup/dump.sql")?;
+    ///     io::copy(&mut reader, &mut File::create("/tmp/dump.sql")?)?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn open_entry<'a>(
+        &'a mut self,
+        entry_path: impl AsRef<Path>,
+    ) -> Result<Box<dyn Read + 'a>> {
+        let entry = self.entry_by_name(entry_path)?;
+        if !entry.is_file() {
+            Err(Error::EntryTypeNotSupported(entry.entry_type.clone()))?;
+        }
+        self.archived()?.entry_reader(&entry)
+    }
+
+    /// Returns a reader streaming the decompressed contents of an entry of
+    /// the archive, without loading them in memory.
+    ///
+    /// The returned reader borrows the archive mutably, so only one entry
+    /// can be read at a time: it must be dropped before the archive is used
+    /// again.
+    ///
+    /// Returns [`Error::EntryTypeNotSupported`] if the entry is not a
+    /// regular file.
+    ///
+    /// # Arguments
+    ///
+    /// - `entry`: the entry to read
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arkiv::{Archive, Result};
+    /// use std::{fs::File, io};
+    ///
+    /// fn main() -> Result<()> {
+    ///     let mut archive = Archive::open("path/to/archive.tgz")?;
+    ///     let entry = archive.entry_by_name("backup/dump.sql")?;
+    ///     let mut reader = archive.entry_reader(&entry)?;
+    ///     io::copy(&mut reader, &mut File::create("/tmp/dump.sql")?)?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn entry_reader(&mut self, entry: &Entry) -> Result<impl Read + '_> {
+        if !entry.is_file() {
+            Err(Error::EntryTypeNotSupported(entry.entry_type.clone()))?;
+        }
+        self.archived()?.entry_reader(entry)
+    }
+
+    /// Streams the decompressed contents of an entry of the archive into a
+    /// writer (e.g. a `BufWriter`, a compressor or a network connection),
+    /// without loading them in memory nor touching the filesystem. Returns
+    /// the number of bytes written.
+    ///
+    /// Returns [`Error::EntryTypeNotSupported`] if the entry is not a
+    /// regular file.
+    ///
+    /// # Arguments
+    ///
+    /// - `entry`: the entry to unpack
+    /// - `writer`: the writer receiving the contents of the entry
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arkiv::{Archive, Result};
+    /// use std::{fs::File, io::BufWriter};
+    ///
+    /// fn main() -> Result<()> {
+    ///     let mut archive = Archive::open("path/to/archive.tgz")?;
+    ///     let entry = archive.entry_by_name("backup/dump.sql")?;
+    ///     let mut writer = BufWriter::new(File::create("/tmp/dump.sql")?);
+    ///     let size = archive.unpack_entry_to_writer(&entry, &mut writer)?;
+    ///     println!("{size} bytes written");
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn unpack_entry_to_writer<W: Write>(
+        &mut self,
+        entry: &Entry,
+        writer: &mut W,
+    ) -> Result<u64> {
+        Ok(io::copy(&mut self.entry_reader(entry)?, writer)?)
+    }
+
+    /// Reads the decompressed contents of a single file of the archive
+    /// directly into memory, without touching the filesystem.
+    ///
+    /// Returns [`Error::FileNotFound`] if the archive contains no entry at
+    /// this path, and [`Error::EntryTypeNotSupported`] if the entry is not
+    /// a regular file.
+    ///
+    /// # Arguments
+    ///
+    /// - `entry_path`: the path of the entry to read
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arkiv::{Archive, Result};
+    ///
+    /// fn main() -> Result<()> {
+    ///     let mut archive = Archive::open("path/to/archive.tgz")?;
+    ///     let bytes = archive.read_entry_to_bytes("some/file_in_the_archive.txt")?;
+    ///     println!("{} bytes", bytes.len());
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn read_entry_to_bytes(&mut self, entry_path: impl AsRef<Path>) -> Result<Vec<u8>> {
+        let entry = self.entry_by_name(entry_path)?;
+        self.read_entry(&entry)
+    }
+
+    /// Reads the decompressed contents of an entry of the archive directly
+    /// into memory, without touching the filesystem.
+    ///
+    /// Returns [`Error::EntryTypeNotSupported`] if the entry is not a
+    /// regular file.
+    ///
+    /// # Arguments
+    ///
+    /// - `entry`: the entry to read
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arkiv::{Archive, Result};
+    ///
+    /// fn main() -> Result<()> {
+    ///     let mut archive = Archive::open("path/to/archive.tgz")?;
+    ///     let manifests = archive
+    ///         .find(|entry| entry.path().ends_with("manifest.json"))?
+    ///         .collect::<Result<Vec<_>>>()?;
+    ///     for manifest in manifests {
+    ///         let bytes = archive.read_entry(&manifest)?;
+    ///         println!("{}: {} bytes", manifest.path().display(), bytes.len());
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn read_entry(&mut self, entry: &Entry) -> Result<Vec<u8>> {
+        let mut bytes = vec![];
+        self.entry_reader(entry)?.read_to_end(&mut bytes)?;
+        Ok(bytes)
+    }
+
+    /// Reads the contents of a single text file of the archive directly
+    /// into a `String`, without touching the filesystem.
+    ///
+    /// Returns [`Error::InvalidEncoding`] if the contents are not valid
+    /// UTF-8, see [`read_entry_to_bytes`](Self::read_entry_to_bytes) for the
+    /// other errors.
+    ///
+    /// # Arguments
+    ///
+    /// - `entry_path`: the path of the entry to read
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arkiv::{Archive, Result};
+    ///
+    /// fn main() -> Result<()> {
+    ///     let mut archive = Archive::open("path/to/archive.tgz")?;
+    ///     let license = archive.read_entry_to_string("LICENSE")?;
+    ///     println!("{license}");
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn read_entry_to_string(&mut self, entry_path: impl AsRef<Path>) -> Result<String> {
+        let entry_path = entry_path.as_ref();
+        let bytes = self.read_entry_to_bytes(entry_path)?;
+        String::from_utf8(bytes).map_err(|_| Error::InvalidEncoding(entry_path.to_path_buf()))
+    }
+}
+
+impl TryFrom<PathBuf> for Archive {
+    type Error = Error;
+
+    /// Opens an archive stored on the filesystem, see [`Archive::open`].
+    fn try_from(path: PathBuf) -> Result<Self> {
+        Archive::open(path)
+    }
+}
+
+impl TryFrom<String> for Archive {
+    type Error = Error;
+
+    /// Opens an archive stored on the filesystem, see [`Archive::open`].
+    fn try_from(path: String) -> Result<Self> {
+        Archive::open(path)
     }
 }
@@ -0,0 +1,529 @@
+use std::{
+    fs::{create_dir_all, File},
+    io::{self, Cursor, Read, Seek, Write},
+    ops::RangeInclusive,
+    path::{Component, Path, PathBuf},
+};
+
+#[cfg(feature = "zip")]
+use zip::{write::FileOptions, CompressionMethod, ZipWriter};
+
+#[cfg(all(feature = "tar", feature = "gzip"))]
+use flate2::write::GzEncoder;
+
+#[cfg(all(feature = "tar", feature = "bzip2"))]
+use bzip2::write::BzEncoder;
+
+#[cfg(all(feature = "tar", feature = "xz"))]
+use xz2::write::XzEncoder;
+
+#[cfg(all(feature = "tar", feature = "zstd"))]
+use zstd::stream::Encoder as ZstdEncoder;
+
+use crate::{Error, Format, Result};
+
+/// permissions of the files added from memory
+const FILE_MODE: u32 = 0o644;
+
+/// permissions of the directories
+const DIRECTORY_MODE: u32 = 0o755;
+
+/// contents of an entry to be added to the archive
+#[derive(Debug, Clone)]
+enum Source {
+    File(PathBuf),
+    Bytes(Vec<u8>),
+    Directory,
+}
+
+/// Compression of the archives created by an [`ArchiveBuilder`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CompressionMode {
+    /// Compress the archive with the compression method of its format.
+    #[default]
+    Default,
+    /// Do not compress the archive at all, for the fastest unpacking: the
+    /// entries of zip archives are stored as is, and tar based archives are
+    /// written as plain [`Format::Tar`] archives.
+    None,
+}
+
+/// Builder used to create a new archive from scratch.
+///
+/// The entries are only written when the archive is finished, using
+/// [`finish`](Self::finish) or [`finish_to_bytes`](Self::finish_to_bytes).
+/// Zip archives require the `zip` feature, tar based archives require the
+/// `tar` feature along with the feature of their compression method.
+///
+/// # Example
+///
+/// ```no_run
+/// use arkiv::{ArchiveBuilder, Format, Result};
+/// use std::path::Path;
+///
+/// fn main() -> Result<()> {
+///     let mut builder = ArchiveBuilder::new(Format::TarGzip).with_compression_level(9);
+///     builder
+///         .add_directory(Path::new("backup/"))?
+///         .add_file(Path::new("/var/backups/dump.sql"))?
+///         .add_bytes(Path::new("backup/README.txt"), b"nightly backup\n")?;
+///     builder.finish(Path::new("/tmp/backup.tar.gz"))?;
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ArchiveBuilder {
+    format: Format,
+    compression_level: Option<u32>,
+    compression: CompressionMode,
+    create_parents: bool,
+    #[cfg(all(feature = "tar", feature = "zstd"))]
+    zstd_dictionary: Option<Vec<u8>>,
+    entries: Vec<(PathBuf, Source)>,
+}
+
+impl ArchiveBuilder {
+    /// Creates a new empty archive of the given format.
+    ///
+    /// # Arguments
+    ///
+    /// - `format`: the format of the archive to create
+    pub fn new(format: Format) -> Self {
+        ArchiveBuilder {
+            format,
+            compression_level: None,
+            compression: CompressionMode::Default,
+            create_parents: false,
+            #[cfg(all(feature = "tar", feature = "zstd"))]
+            zstd_dictionary: None,
+            entries: vec![],
+        }
+    }
+
+    /// Sets the compression level of the archive. Its meaning depends on the
+    /// format: `0..=9` for zip, gzip and xz, `1..=9` for bzip2 and `1..=22`
+    /// for zstd. Out of range levels are clamped to the closest valid level,
+    /// and the level is ignored for plain tar archives.
+    ///
+    /// # Arguments
+    ///
+    /// - `level`: the compression level
+    pub fn with_compression_level(mut self, level: u32) -> Self {
+        self.compression_level = Some(level);
+        self
+    }
+
+    /// Sets the compression of the archive, see [`CompressionMode`]. Without
+    /// compression, the compression level is ignored and tar based archives
+    /// are written as plain tar archives (e.g. a [`Format::TarGzip`] archive
+    /// should then be named `.tar` rather than `.tar.gz`), see
+    /// [`format`](Self::format).
+    ///
+    /// # Arguments
+    ///
+    /// - `compression`: the compression of the archive
+    pub fn with_compression(mut self, compression: CompressionMode) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    /// Shorthand for `with_compression(CompressionMode::None)`, for archives
+    /// meant to be unpacked as fast as possible rather than to be small.
+    pub fn no_compression(self) -> Self {
+        self.with_compression(CompressionMode::None)
+    }
+
+    /// Returns the format of the archive that is written, i.e. the format
+    /// passed to [`new`](Self::new) unless a tar based archive is not
+    /// compressed, in which case it is a plain [`Format::Tar`] archive.
+    pub fn format(&self) -> Format {
+        if self.compression == CompressionMode::None && self.format.is_tar() {
+            Format::Tar
+        } else {
+            self.format.clone()
+        }
+    }
+
+    /// Creates the missing parent directories of the destination when the
+    /// archive is finished with [`finish`](Self::finish). By default, a
+    /// missing parent directory is reported as an error.
+    ///
+    /// # Arguments
+    ///
+    /// - `create_parents`: whether to create the missing parent directories
+    pub fn with_create_parents(mut self, create_parents: bool) -> Self {
+        self.create_parents = create_parents;
+        self
+    }
+
+    /// Compresses the archive using a zstd dictionary, e.g. trained on
+    /// representative samples with `zstd --train`, to achieve better
+    /// compression ratios on small files. This only has an effect on
+    /// [`Format::TarZstd`] archives.
+    ///
+    /// The dictionary is not stored in the archive, it must be provided
+    /// separately to decompress it, see
+    /// [`ArchiveOptions::zstd_dictionary`](crate::ArchiveOptions::zstd_dictionary).
+    ///
+    /// # Arguments
+    ///
+    /// - `dict`: the contents of the dictionary
+    #[cfg(all(feature = "tar", feature = "zstd"))]
+    pub fn with_zstd_dictionary(mut self, dict: &[u8]) -> Self {
+        self.zstd_dictionary = Some(dict.to_vec());
+        self
+    }
+
+    /// Adds a file of the filesystem at the root of the archive, under its
+    /// file name. The file is only read when the archive is finished.
+    ///
+    /// # Arguments
+    ///
+    /// - `src_path`: the path of the file to add
+    pub fn add_file(&mut self, src_path: &Path) -> Result<&mut Self> {
+        if !src_path.metadata()?.is_file() {
+            Err(invalid_input(src_path, "not a file"))?;
+        }
+        let name = src_path
+            .file_name()
+            .ok_or_else(|| invalid_input(src_path, "invalid file name"))?;
+        self.push(Path::new(name), Source::File(src_path.to_path_buf()))
+    }
+
+    /// Adds a file of the filesystem to the archive under the given path,
+    /// preserving its size and permissions. The file is only read when the
+    /// archive is finished.
+    ///
+    /// # Arguments
+    ///
+    /// - `name`: the path of the file in the archive
+    /// - `src_path`: the path of the file to add
+    pub fn add_path(&mut self, name: &Path, src_path: &Path) -> Result<&mut Self> {
+        if !src_path.metadata()?.is_file() {
+            Err(invalid_input(src_path, "not a file"))?;
+        }
+        self.push(name, Source::File(src_path.to_path_buf()))
+    }
+
+    /// Adds a file with the given contents to the archive.
+    ///
+    /// # Arguments
+    ///
+    /// - `name`: the path of the file in the archive
+    /// - `data`: the contents of the file
+    pub fn add_bytes(&mut self, name: &Path, data: &[u8]) -> Result<&mut Self> {
+        self.push(name, Source::Bytes(data.to_vec()))
+    }
+
+    /// Adds a file to the archive, its contents being read from `reader`.
+    /// Unlike [`add_file`](Self::add_file), the reader is consumed right
+    /// away and its contents are kept in memory until the archive is
+    /// finished.
+    ///
+    /// # Arguments
+    ///
+    /// - `name`: the path of the file in the archive
+    /// - `reader`: the reader providing the contents of the file
+    pub fn add_reader(&mut self, name: &Path, mut reader: impl Read) -> Result<&mut Self> {
+        let mut data = vec![];
+        reader.read_to_end(&mut data)?;
+        self.push(name, Source::Bytes(data))
+    }
+
+    /// Adds an empty directory to the archive.
+    ///
+    /// # Arguments
+    ///
+    /// - `name`: the path of the directory in the archive
+    pub fn add_directory(&mut self, name: &Path) -> Result<&mut Self> {
+        self.push(name, Source::Directory)
+    }
+
+    /// Writes the archive to a file, which is overwritten if it already exists.
+    ///
+    /// Returns [`Error::UnsupportedArchive`] if archives of this format cannot
+    /// be created, and [`Error::Io`] if the parent directory of `dest` does
+    /// not exist, unless [`with_create_parents`](Self::with_create_parents)
+    /// is used.
+    ///
+    /// # Arguments
+    ///
+    /// - `dest`: the path of the archive to create
+    pub fn finish(&self, dest: &Path) -> Result<()> {
+        // the parent of a bare file name is empty, i.e. the current directory
+        let missing_parent = dest
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty() && !parent.exists());
+        if let Some(parent) = missing_parent {
+            if self.create_parents {
+                create_dir_all(parent)?;
+            } else {
+                let message = format!("parent directory not found: '{}'", parent.display());
+                Err(io::Error::new(io::ErrorKind::NotFound, message))?;
+            }
+        }
+        let mut file = self.write(File::create(dest)?)?;
+        file.flush()?;
+        Ok(())
+    }
+
+    /// Writes the archive in memory and returns its bytes.
+    ///
+    /// Returns [`Error::UnsupportedArchive`] if archives of this format cannot
+    /// be created.
+    pub fn finish_to_bytes(&self) -> Result<Vec<u8>> {
+        Ok(self.write(Cursor::new(vec![]))?.into_inner())
+    }
+
+    /// Writes the archive in chunks of `chunk_size` bytes, e.g. to upload it
+    /// as the parts of a multipart upload to an object storage service. The
+    /// callback is called with the offset of each chunk within the archive
+    /// and its contents. All the chunks are `chunk_size` bytes long, except
+    /// the last one which may be shorter.
+    ///
+    /// The archive is streamed and never held in memory as a whole, which
+    /// is only possible for tar based archives: writing a zip archive
+    /// requires to seek back to the start of each entry once its contents
+    /// are written.
+    ///
+    /// Returns [`Error::UnsupportedArchive`] for zip archives, and if archives
+    /// of this format cannot be created.
+    ///
+    /// # Arguments
+    ///
+    /// - `chunk_size`: the size of the chunks, in bytes (at least 1)
+    /// - `callback`: closure called with the offset and the contents of each chunk
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arkiv::{ArchiveBuilder, Format, Result};
+    /// use std::path::Path;
+    ///
+    /// fn main() -> Result<()> {
+    ///     let mut builder = ArchiveBuilder::new(Format::TarGzip);
+    ///     builder.add_file(Path::new("/var/backups/dump.sql"))?;
+    ///     builder.finish_in_chunks(5 * 1024 * 1024, |offset, chunk| {
+    ///         println!("uploading {} bytes at offset {offset}", chunk.len());
+    ///     })?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn finish_in_chunks(
+        &self,
+        chunk_size: u64,
+        callback: impl FnMut(u64, &[u8]),
+    ) -> Result<()> {
+        if self.format == Format::Zip {
+            Err(Error::unsupported_archive(
+                "zip archives cannot be written in chunks",
+            ))?;
+        }
+        let writer = ChunkWriter {
+            chunk: Vec::new(),
+            chunk_size: chunk_size.max(1) as usize,
+            offset: 0,
+            callback,
+        };
+        let mut writer = self.write_stream(writer)?;
+        writer.flush_chunk();
+        Ok(())
+    }
+
+    fn push(&mut self, name: &Path, source: Source) -> Result<&mut Self> {
+        let mut components = name.components().peekable();
+        if components.peek().is_none()
+            || !components.all(|component| matches!(component, Component::Normal(_)))
+        {
+            Err(invalid_input(name, "invalid entry name"))?;
+        }
+        self.entries.push((name.to_path_buf(), source));
+        Ok(self)
+    }
+
+    /// compression level of the archive, clamped to the range of the format
+    #[allow(unused)]
+    fn level(&self, range: RangeInclusive<u32>, default: u32) -> u32 {
+        let level = self.compression_level.unwrap_or(default);
+        level.clamp(*range.start(), *range.end())
+    }
+
+    fn write<W: Write + Seek>(&self, writer: W) -> Result<W> {
+        match self.format {
+            #[cfg(feature = "zip")]
+            Format::Zip => self.write_zip(writer),
+            _ => self.write_stream(writer),
+        }
+    }
+
+    /// writes the formats that do not need to seek, i.e. tar based archives
+    #[allow(unused)]
+    fn write_stream<W: Write>(&self, writer: W) -> Result<W> {
+        match self.format() {
+            #[cfg(feature = "tar")]
+            Format::Tar => self.write_tar(writer),
+            #[cfg(all(feature = "tar", feature = "gzip"))]
+            Format::TarGzip => {
+                let level = flate2::Compression::new(self.level(0..=9, 6));
+                Ok(self.write_tar(GzEncoder::new(writer, level))?.finish()?)
+            }
+            #[cfg(all(feature = "tar", feature = "bzip2"))]
+            Format::TarBzip2 => {
+                let level = bzip2::Compression::new(self.level(1..=9, 6));
+                Ok(self.write_tar(BzEncoder::new(writer, level))?.finish()?)
+            }
+            #[cfg(all(feature = "tar", feature = "xz"))]
+            Format::TarXz2 => {
+                let level = self.level(0..=9, 6);
+                Ok(self.write_tar(XzEncoder::new(writer, level))?.finish()?)
+            }
+            #[cfg(all(feature = "tar", feature = "zstd"))]
+            Format::TarZstd => {
+                let level = self.level(1..=22, 3) as i32;
+                let encoder = match &self.zstd_dictionary {
+                    Some(dict) => ZstdEncoder::with_dictionary(writer, level, dict)?,
+                    None => ZstdEncoder::new(writer, level)?,
+                };
+                Ok(self.write_tar(encoder)?.finish()?)
+            }
+            _ => Err(Error::unsupported_archive(
+                "archives of this format cannot be created",
+            )),
+        }
+    }
+
+    #[cfg(feature = "zip")]
+    fn write_zip<W: Write + Seek>(&self, writer: W) -> Result<W> {
+        let mut zip = ZipWriter::new(writer);
+        let options = match self.compression {
+            CompressionMode::Default => FileOptions::default()
+                .compression_method(CompressionMethod::Deflated)
+                .compression_level(Some(self.level(0..=9, 6) as i32)),
+            CompressionMode::None => {
+                FileOptions::default().compression_method(CompressionMethod::Stored)
+            }
+        };
+
+        for (name, source) in &self.entries {
+            let name = entry_name(name, source);
+            match source {
+                Source::File(src_path) => {
+                    let mut file = File::open(src_path)?;
+                    let metadata = file.metadata()?;
+                    let options = options
+                        .unix_permissions(file_mode(&metadata))
+                        .large_file(metadata.len() >= u32::MAX as u64);
+                    zip.start_file(name, options)?;
+                    io::copy(&mut file, &mut zip)?;
+                }
+                Source::Bytes(data) => {
+                    zip.start_file(name, options.unix_permissions(FILE_MODE))?;
+                    zip.write_all(data)?;
+                }
+                Source::Directory => {
+                    zip.add_directory(name, options.unix_permissions(DIRECTORY_MODE))?;
+                }
+            }
+        }
+        Ok(zip.finish()?)
+    }
+
+    #[cfg(feature = "tar")]
+    fn write_tar<W: Write>(&self, writer: W) -> Result<W> {
+        let mut tar = tar::Builder::new(writer);
+        for (name, source) in &self.entries {
+            let name = entry_name(name, source);
+            match source {
+                Source::File(src_path) => tar.append_path_with_name(src_path, name)?,
+                Source::Bytes(data) => {
+                    let mut header = tar_header(tar::EntryType::Regular, FILE_MODE, data.len());
+                    tar.append_data(&mut header, name, data.as_slice())?;
+                }
+                Source::Directory => {
+                    let mut header = tar_header(tar::EntryType::Directory, DIRECTORY_MODE, 0);
+                    tar.append_data(&mut header, name, io::empty())?;
+                }
+            }
+        }
+        Ok(tar.into_inner()?)
+    }
+}
+
+/// writer passing the data written to a callback, in chunks of a fixed size
+struct ChunkWriter<F: FnMut(u64, &[u8])> {
+    chunk: Vec<u8>,
+    chunk_size: usize,
+    offset: u64,
+    callback: F,
+}
+
+impl<F: FnMut(u64, &[u8])> ChunkWriter<F> {
+    fn flush_chunk(&mut self) {
+        if !self.chunk.is_empty() {
+            (self.callback)(self.offset, &self.chunk);
+            self.offset += self.chunk.len() as u64;
+            self.chunk.clear();
+        }
+    }
+}
+
+impl<F: FnMut(u64, &[u8])> Write for ChunkWriter<F> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let len = buf.len().min(self.chunk_size - self.chunk.len());
+        self.chunk.extend_from_slice(&buf[..len]);
+        if self.chunk.len() == self.chunk_size {
+            self.flush_chunk();
+        }
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        // partial chunks are only passed to the callback once the archive is finished
+        Ok(())
+    }
+}
+
+/// name of the entry in the archive, using `/` as separator and ending with
+/// a `/` for directories
+#[allow(unused)]
+fn entry_name(name: &Path, source: &Source) -> String {
+    let mut name = name
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/");
+    if let Source::Directory = source {
+        name.push('/');
+    }
+    name
+}
+
+fn invalid_input(path: &Path, reason: &str) -> io::Error {
+    let message = format!("{reason}: '{}'", path.display());
+    io::Error::new(io::ErrorKind::InvalidInput, message)
+}
+
+#[cfg(all(feature = "zip", unix))]
+fn file_mode(metadata: &std::fs::Metadata) -> u32 {
+    use std::os::unix::fs::PermissionsExt;
+    metadata.permissions().mode() & 0o7777
+}
+
+#[cfg(all(feature = "zip", not(unix)))]
+fn file_mode(_metadata: &std::fs::Metadata) -> u32 {
+    FILE_MODE
+}
+
+#[cfg(feature = "tar")]
+fn tar_header(entry_type: tar::EntryType, mode: u32, size: usize) -> tar::Header {
+    let mtime = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default();
+    let mut header = tar::Header::new_gnu();
+    header.set_entry_type(entry_type);
+    header.set_mode(mode);
+    header.set_size(size as u64);
+    header.set_mtime(mtime);
+    header
+}
@@ -0,0 +1,100 @@
+use std::{
+    fs::{create_dir_all, File},
+    io::{self, ErrorKind, Read},
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    archive::{Archived, OnUnpacked, Visitor},
+    entry::EntryType,
+    Entries, Entry, Error, Result,
+};
+
+/// backend for a single compressed file (e.g. `sample.txt.gz`), seen as an
+/// archive containing a single entry named after the file without its
+/// compression extension (e.g. `sample.txt`).
+pub(crate) struct Compressed<R> {
+    reader: R,
+    path: PathBuf,
+}
+
+impl<R: Read> Compressed<R> {
+    pub(crate) fn new(reader: R, path: &Path) -> Self {
+        let path = path.file_stem().map(PathBuf::from).unwrap_or_default();
+        Compressed { reader, path }
+    }
+
+    fn entry(&self) -> Entry {
+        Entry {
+            index: 0,
+            path: self.path.clone(),
+            // the uncompressed size is not known without decompressing the file
+            size: 0,
+            compressed_size: None,
+            entry_type: EntryType::File,
+            creator_os: None,
+            unix_mode: None,
+            modified: None,
+            uid: None,
+            gid: None,
+            uname: None,
+            gname: None,
+        }
+    }
+
+    fn check_entry(&self, entry: &Entry) -> Result<()> {
+        if entry.index() != 0 || entry.path() != self.path {
+            Err(Error::FileNotFound)?;
+        }
+        Ok(())
+    }
+}
+
+impl<R: Read> Archived for Compressed<R> {
+    fn unpack(&mut self, dest: &Path, on_unpacked: &mut OnUnpacked) -> Result<()> {
+        let entry = self.entry();
+        self.unpack_entry(&entry, dest)?;
+        on_unpacked();
+        Ok(())
+    }
+
+    fn entries(&mut self) -> Result<Entries> {
+        Ok(Box::new(std::iter::once(Ok(self.entry()))))
+    }
+
+    fn unpack_entry(&mut self, entry: &Entry, dest: &Path) -> Result<()> {
+        self.check_entry(entry)?;
+        create_dir_all(dest)?;
+        let mut outfile = File::create(dest.join(&self.path))?;
+        io::copy(&mut self.reader, &mut outfile)?;
+        Ok(())
+    }
+
+    fn walk(&mut self, visit: &mut Visitor) -> Result<()> {
+        let entry = self.entry();
+        // there is no other entry to visit, whatever the visitor returns
+        let _ = visit(entry, &mut self.reader)?;
+        Ok(())
+    }
+
+    fn entry_reader(&mut self, entry: &Entry) -> Result<Box<dyn Read + '_>> {
+        self.check_entry(entry)?;
+        Ok(Box::new(&mut self.reader))
+    }
+
+    fn verify_entry(&mut self, entry: &Entry) -> Result<bool> {
+        self.check_entry(entry)?;
+        // the decoders check the integrity of the stream while decompressing it
+        match io::copy(&mut self.reader, &mut io::sink()) {
+            Ok(_) => Ok(true),
+            Err(err) if matches!(err.kind(), ErrorKind::InvalidData | ErrorKind::InvalidInput) => {
+                Ok(false)
+            }
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    fn entry_count_hint(&self) -> Option<usize> {
+        Some(1)
+    }
+}
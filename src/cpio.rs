@@ -0,0 +1,236 @@
+use std::{
+    fs::create_dir_all,
+    io::{self, Read},
+    path::{Path, PathBuf},
+    time::{Duration, UNIX_EPOCH},
+};
+
+use crate::{
+    archive::{extract, Archived, OnUnpacked, Visitor},
+    entry::EntryType,
+    Entries, Entry, Error, Result,
+};
+
+/// size of the header of an entry in the "new ASCII" format
+const HEADER_SIZE: usize = 110;
+
+/// name of the entry marking the end of the archive
+const TRAILER: &str = "TRAILER!!!";
+
+/// bit mask and values of the type of an entry in its mode
+const S_IFMT: u32 = 0o170000;
+const S_IFREG: u32 = 0o100000;
+const S_IFDIR: u32 = 0o040000;
+const S_IFLNK: u32 = 0o120000;
+
+/// number of bytes needed to align `len` on a multiple of 4
+fn padding(len: u64) -> u64 {
+    (4 - len % 4) % 4
+}
+
+/// `cpio` archive backend in the "new ASCII" format (e.g. the payload of
+/// rpm packages), which is read sequentially like a tar archive.
+///
+/// Hard links are stored as regular files sharing the same inode, the
+/// contents being stored with the last one only, so the other ones are
+/// seen as empty files.
+pub(crate) struct Cpio<R> {
+    /// underlying reader, limited to the unread data of the current entry
+    reader: io::Take<R>,
+    /// padding following the data of the current entry
+    padding: u64,
+    /// checksum of the data of the current entry, in the "crc" format only
+    check: Option<u32>,
+    index: usize,
+    done: bool,
+}
+
+impl<R: Read> Cpio<R> {
+    pub(crate) fn new(reader: R) -> Self {
+        Cpio {
+            reader: reader.take(0),
+            padding: 0,
+            check: None,
+            index: 0,
+            done: false,
+        }
+    }
+
+    /// skips the rest of the current entry and reads the header of the next
+    /// one, `None` once the end of the archive is reached
+    fn next_entry(&mut self) -> Result<Option<Entry>> {
+        if self.done {
+            return Ok(None);
+        }
+        io::copy(&mut self.reader, &mut io::sink())?;
+        let inner = self.reader.get_mut();
+        let skipped = io::copy(&mut inner.by_ref().take(self.padding), &mut io::sink())?;
+        if self.reader.limit() != 0 || skipped != self.padding {
+            Err(Error::invalid_archive("truncated cpio archive"))?;
+        }
+
+        let invalid_header = || Error::invalid_archive("invalid cpio header");
+        let inner = self.reader.get_mut();
+        let mut header = [0; HEADER_SIZE];
+        inner
+            .read_exact(&mut header)
+            .map_err(|_| invalid_header())?;
+        let check = match &header[..6] {
+            b"070701" => false,
+            b"070702" => true,
+            _ => Err(invalid_header())?,
+        };
+        let field = |index: usize| {
+            let start = 6 + 8 * index;
+            std::str::from_utf8(&header[start..start + 8])
+                .ok()
+                .and_then(|field| u32::from_str_radix(field, 16).ok())
+                .ok_or_else(invalid_header)
+        };
+        let mode = field(1)?;
+        let size = u64::from(field(6)?);
+        let name_size = u64::from(field(11)?);
+
+        let mut name = vec![];
+        inner.by_ref().take(name_size).read_to_end(&mut name)?;
+        if name.len() as u64 != name_size || name.pop() != Some(0) {
+            Err(invalid_header())?;
+        }
+        let name =
+            String::from_utf8(name).map_err(|_| Error::invalid_archive("invalid filename"))?;
+        let name_padding = padding(HEADER_SIZE as u64 + name_size);
+        io::copy(&mut inner.by_ref().take(name_padding), &mut io::sink())?;
+        if name == TRAILER {
+            self.done = true;
+            return Ok(None);
+        }
+
+        self.reader.set_limit(size);
+        self.padding = padding(size);
+        self.check = check.then_some(field(12)?);
+
+        let mut path = PathBuf::from(name);
+        let entry_type = match mode & S_IFMT {
+            S_IFREG => EntryType::File,
+            S_IFDIR => {
+                // cpio does not store the trailing slash of directories, unlike zip and tar
+                if !path.as_os_str().to_string_lossy().ends_with('/') {
+                    path.as_mut_os_string().push("/");
+                }
+                EntryType::Directory
+            }
+            S_IFLNK => {
+                let mut target = vec![];
+                self.reader.read_to_end(&mut target)?;
+                let target = String::from_utf8(target)
+                    .map_err(|_| Error::invalid_archive("invalid symbolic link target"))?;
+                EntryType::Symlink {
+                    target: PathBuf::from(target),
+                }
+            }
+            _ => EntryType::Other,
+        };
+        let entry = Entry {
+            index: self.index,
+            path,
+            size,
+            compressed_size: None,
+            entry_type,
+            creator_os: None,
+            unix_mode: Some(mode & 0o7777),
+            modified: Some(UNIX_EPOCH + Duration::from_secs(field(5)?.into())),
+            uid: Some(field(2)?.into()),
+            gid: Some(field(3)?.into()),
+            uname: None,
+            gname: None,
+        };
+        self.index += 1;
+        Ok(Some(entry))
+    }
+
+    /// moves to the entry matching `entry` with a single forward scan of the archive
+    fn seek_entry(&mut self, entry: &Entry) -> Result<()> {
+        while let Some(current) = self.next_entry()? {
+            if current.index() == entry.index() {
+                if current.path() != entry.path() {
+                    Err(Error::FileNotFound)?;
+                }
+                return Ok(());
+            }
+        }
+        Err(Error::FileNotFound)
+    }
+}
+
+struct CpioEntries<'a, R>(&'a mut Cpio<R>);
+
+impl<'a, R: Read> Iterator for CpioEntries<'a, R> {
+    type Item = Result<Entry>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let entry = self.0.next_entry();
+        if entry.is_err() {
+            // the position of the next header is unknown after an error
+            self.0.done = true;
+        }
+        entry.transpose()
+    }
+}
+
+impl<R: Read> Archived for Cpio<R> {
+    fn unpack(&mut self, dest: &Path, on_unpacked: &mut OnUnpacked) -> Result<()> {
+        create_dir_all(dest)?;
+        while let Some(entry) = self.next_entry()? {
+            // the other types of entries (e.g. devices) are skipped
+            extract(&entry, &mut self.reader, dest)?;
+            on_unpacked();
+        }
+        Ok(())
+    }
+
+    fn entries(&mut self) -> Result<Entries> {
+        Ok(Box::new(CpioEntries(self)))
+    }
+
+    fn unpack_entry(&mut self, entry: &Entry, dest: &Path) -> Result<()> {
+        if entry.is_other() {
+            Err(Error::EntryTypeNotSupported(entry.entry_type.clone()))?;
+        }
+        self.seek_entry(entry)?;
+        extract(entry, &mut self.reader, dest)
+    }
+
+    fn walk(&mut self, visit: &mut Visitor) -> Result<()> {
+        while let Some(entry) = self.next_entry()? {
+            if visit(entry, &mut self.reader)?.is_break() {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    fn entry_reader(&mut self, entry: &Entry) -> Result<Box<dyn Read + '_>> {
+        self.seek_entry(entry)?;
+        Ok(Box::new(&mut self.reader))
+    }
+
+    fn verify_entry(&mut self, entry: &Entry) -> Result<bool> {
+        self.seek_entry(entry)?;
+        // the checksum, only stored in the "crc" format, is the sum of the bytes of the data
+        let mut sum = 0u32;
+        let mut buffer = [0; 8192];
+        loop {
+            let len = self.reader.read(&mut buffer)?;
+            if len == 0 {
+                break;
+            }
+            sum = buffer[..len]
+                .iter()
+                .fold(sum, |sum, byte| sum.wrapping_add(u32::from(*byte)));
+        }
+        match self.check {
+            Some(check) => Ok(check == sum),
+            None => Ok(true),
+        }
+    }
+}
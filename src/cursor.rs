@@ -0,0 +1,72 @@
+use std::io::Read;
+
+use crate::{Archive, Entry, Error, Result};
+
+/// A cursor over the entries of an archive that can be moved to any index.
+///
+/// The cursor starts before the first entry. For zip archives, moving
+/// the cursor only looks up the central directory, so jumping around
+/// large archives is cheap. Tar archives are streamed, so each move
+/// re-reads the archive from the start up to the requested entry.
+///
+/// See [`Archive::cursor`].
+pub struct EntryCursor<'a> {
+    pub(crate) archive: &'a mut Archive,
+    pub(crate) index: Option<usize>,
+    pub(crate) current: Option<Entry>,
+}
+
+impl<'a> EntryCursor<'a> {
+    /// Returns the entry the cursor is currently pointing at, if any.
+    pub fn current(&self) -> Option<&Entry> {
+        self.current.as_ref()
+    }
+
+    /// Moves the cursor to the entry at a given index and returns it.
+    ///
+    /// If the index is out of range, `None` is returned and the cursor
+    /// is left unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// - `index`: the index of the entry within the archive
+    pub fn seek_to(&mut self, index: usize) -> Result<Option<&Entry>> {
+        let entry = self.archive.archived()?.entries()?.nth(index).transpose()?;
+        if entry.is_some() {
+            self.index = Some(index);
+            self.current = entry;
+            Ok(self.current.as_ref())
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Moves the cursor to the next entry and returns it.
+    ///
+    /// If the cursor points at the last entry, `None` is returned and
+    /// the cursor is left unchanged.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Result<Option<&Entry>> {
+        let index = self.index.map_or(0, |index| index + 1);
+        self.seek_to(index)
+    }
+
+    /// Moves the cursor to the previous entry and returns it.
+    ///
+    /// If the cursor points at the first entry, `None` is returned and
+    /// the cursor is left unchanged.
+    pub fn prev(&mut self) -> Result<Option<&Entry>> {
+        match self.index {
+            Some(index) if index > 0 => self.seek_to(index - 1),
+            _ => Ok(None),
+        }
+    }
+
+    /// Returns a reader over the contents of the current entry.
+    ///
+    /// Returns [`Error::FileNotFound`] if the cursor does not point at any entry.
+    pub fn read_current(&mut self) -> Result<Box<dyn Read + '_>> {
+        let entry = self.current.as_ref().ok_or(Error::FileNotFound)?;
+        self.archive.archived()?.entry_reader(entry)
+    }
+}
@@ -0,0 +1,96 @@
+use std::{
+    fs::File,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    ar::{read_members, Member},
+    archive::Storage,
+    Archive, Error, Result,
+};
+
+/// A Debian package (`.deb`).
+///
+/// A Debian package is an `ar` archive containing a `debian-binary` file
+/// with the version of the package format, a `control.tar.*` archive with
+/// the metadata of the package and a `data.tar.*` archive with the files
+/// to install. This struct gives access to these inner archives.
+///
+/// This type is only available if the `deb` feature is enabled. Opening the
+/// inner archives requires the `tar` feature along with the feature of their
+/// compression format (e.g. `xz` for `data.tar.xz`).
+///
+/// # Example
+///
+/// ```no_run
+/// use arkiv::{DebPackage, Result};
+///
+/// fn main() -> Result<()> {
+///     let package = DebPackage::open("path/to/package.deb")?;
+///
+///     for entry in package.data()?.entry_paths()? {
+///         println!("{entry}");
+///     }
+///     Ok(())
+/// }
+/// ```
+pub struct DebPackage {
+    path: PathBuf,
+    members: Vec<Member>,
+}
+
+impl DebPackage {
+    /// Opens a Debian package stored on the filesystem.
+    ///
+    /// # Arguments:
+    ///
+    /// - `path`: the path to the package to open
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        if !path.exists() {
+            Err(Error::ArchiveNotFound(path.clone()))?;
+        }
+        let members = read_members(&mut File::open(&path)?)?;
+        if !members.iter().any(|member| member.name == "debian-binary") {
+            Err(Error::invalid_archive(
+                "missing debian-binary in debian package",
+            ))?;
+        }
+        Ok(DebPackage { path, members })
+    }
+
+    /// Returns the path of the package.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Opens the `control.tar.*` archive containing the metadata of the package.
+    pub fn control(&self) -> Result<Archive> {
+        self.inner_archive("control.tar")
+    }
+
+    /// Opens the `data.tar.*` archive containing the files of the package.
+    pub fn data(&self) -> Result<Archive> {
+        self.inner_archive("data.tar")
+    }
+
+    fn inner_archive(&self, stem: &str) -> Result<Archive> {
+        let member = self
+            .members
+            .iter()
+            .find(|member| {
+                member.name == stem
+                    || member
+                        .name
+                        .strip_prefix(stem)
+                        .is_some_and(|ext| ext.starts_with('.'))
+            })
+            .ok_or(Error::FileNotFound)?;
+        Archive::new(Storage::ArMember {
+            path: self.path.clone(),
+            name: member.name.clone(),
+            offset: member.offset,
+            size: member.size,
+        })
+    }
+}
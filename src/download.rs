@@ -1,11 +1,18 @@
-use ureq::Response;
+use flate2::read::GzDecoder;
+use ureq::{Agent, AgentBuilder, Proxy, Response};
 
 use crate::{archive::Storage, Archive};
 
 use super::{Error, Result};
 use std::{
-    io::{ErrorKind, Write},
+    cell::Cell,
+    env,
+    io::{ErrorKind, Read, Write},
+    net::IpAddr,
     path::{Path, PathBuf},
+    rc::Rc,
+    thread,
+    time::Duration,
 };
 
 /// URL is missing in [`Downloader`].
@@ -26,6 +33,9 @@ pub enum DestProvided {
     Dir(PathBuf),
 }
 
+/// Download destination is a temporary directory that outlives the [`Archive`] in [`Downloader`].
+pub struct DestPersistentTempDir;
+
 /// Progress callback is not provided in [`Downloader`]
 pub struct OnProgressNotProvided;
 
@@ -59,8 +69,41 @@ pub struct Downloader<U, D, O> {
     url: U,
     dest: D,
     on_progress: O,
+    options: Options,
+}
+
+/// Callback called by [`Downloader`] once the download is complete.
+type OnComplete = Box<dyn Fn(&Archive) + Send + Sync>;
+
+/// Settings of a [`Downloader`] that do not affect its type.
+struct Options {
+    on_complete: Option<OnComplete>,
+    system_proxy: bool,
+    same_host_redirects: bool,
+    bearer_token_env: Option<String>,
+    retries: u32,
+    retry_backoff: Duration,
 }
 
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            on_complete: None,
+            system_proxy: false,
+            same_host_redirects: false,
+            bearer_token_env: None,
+            retries: 0,
+            retry_backoff: DEFAULT_RETRY_BACKOFF,
+        }
+    }
+}
+
+/// Maximum number of redirects followed by a [`Downloader`].
+const MAX_REDIRECTS: u32 = 5;
+
+/// Delay before the first retry of a [`Downloader`], doubled after every attempt.
+const DEFAULT_RETRY_BACKOFF: Duration = Duration::from_millis(500);
+
 impl Downloader<UrlMissing, DestMissing, OnProgressNotProvided> {
     /// Returns a new [`Downloader`].
     pub fn new() -> Self {
@@ -75,6 +118,7 @@ impl Default for Downloader<UrlMissing, DestMissing, OnProgressNotProvided> {
             url: UrlMissing,
             dest: DestMissing,
             on_progress: OnProgressNotProvided,
+            options: Options::default(),
         }
     }
 }
@@ -89,10 +133,12 @@ impl<D, O> Downloader<UrlMissing, D, O> {
         let url = UrlProvided(url.as_ref().to_string());
         let dest = self.dest;
         let on_progress = self.on_progress;
+        let options = self.options;
         Downloader {
             url,
             dest,
             on_progress,
+            options,
         }
     }
 }
@@ -104,10 +150,30 @@ impl<U, O> Downloader<U, DestMissing, O> {
         let url = self.url;
         let dest = DestProvided::TempDir;
         let on_progress = self.on_progress;
+        let options = self.options;
+        Downloader {
+            url,
+            dest,
+            on_progress,
+            options,
+        }
+    }
+
+    /// Allows to specify that the downloaded archive file should be stored
+    /// in a fresh temporary directory. Unlike with [`to_temp`](Self::to_temp),
+    /// this directory is not removed when the returned [`Archive`] is dropped,
+    /// which allows to cache the downloaded file, e.g. by moving it to a
+    /// permanent location once it has been opened successfully.
+    pub fn to_temp_persistent(self) -> Downloader<U, DestPersistentTempDir, O> {
+        let url = self.url;
+        let dest = DestPersistentTempDir;
+        let on_progress = self.on_progress;
+        let options = self.options;
         Downloader {
             url,
             dest,
             on_progress,
+            options,
         }
     }
 
@@ -122,10 +188,12 @@ impl<U, O> Downloader<U, DestMissing, O> {
         let url = self.url;
         let dest = DestProvided::Dir(dest.as_ref().to_path_buf());
         let on_progress = self.on_progress;
+        let options = self.options;
         Downloader {
             url,
             dest,
             on_progress,
+            options,
         }
     }
 }
@@ -154,11 +222,189 @@ impl<U, D> Downloader<U, D, OnProgressNotProvided> {
         let url = self.url;
         let dest = self.dest;
         let on_progress = OnProgressProvided(callback);
+        let options = self.options;
         Downloader {
             url,
             dest,
             on_progress,
+            options,
+        }
+    }
+}
+
+impl<U, D, O> Downloader<U, D, O> {
+    /// Sets a callback that will be called with the opened [`Archive`] once
+    /// the download has completed successfully, just before it is returned
+    /// by `download`. This can be used to e.g. log or validate the archive.
+    ///
+    /// # Arguments
+    ///
+    /// - `callback`: closure that will be called with the downloaded archive
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arkiv::{Downloader, Result};
+    ///
+    /// pub fn main() -> Result<()> {
+    ///     let url = "https://github.com/meuter/arkiv-rs/raw/main/tests/sample/sample.zip";
+    ///     let mut archive = Downloader::new()
+    ///         .url(url)
+    ///         .to_temp()
+    ///         .on_complete(|archive| println!("downloaded {}", archive.path().display()))
+    ///         .download()?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn on_complete<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&Archive) + Send + Sync + 'static,
+    {
+        self.options.on_complete = Some(Box::new(callback));
+        self
+    }
+
+    /// Uses the proxy configured in the environment, like `curl` or `wget` do.
+    ///
+    /// The proxy is read from `HTTPS_PROXY` or `HTTP_PROXY` depending on the
+    /// scheme of the URL, with `ALL_PROXY` as a fallback. The proxy is bypassed
+    /// if the host of the URL matches one of the comma separated entries of
+    /// `NO_PROXY`, which can be domains (e.g. `localhost`, `.example.com`),
+    /// IP addresses, IP ranges (e.g. `127.0.0.0/8`) or `*`. Lower case
+    /// variable names (e.g. `no_proxy`) are supported as well.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arkiv::{Downloader, Result};
+    ///
+    /// pub fn main() -> Result<()> {
+    ///     let url = "https://github.com/meuter/arkiv-rs/raw/main/tests/sample/sample.zip";
+    ///     let mut archive = Downloader::new()
+    ///         .url(url)
+    ///         .to_temp()
+    ///         .with_system_proxy()
+    ///         .download()?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn with_system_proxy(mut self) -> Self {
+        self.options.system_proxy = true;
+        self
+    }
+
+    /// Enables or disables the restriction of redirects to the host of the
+    /// URL (disabled by default).
+    ///
+    /// When enabled, a redirect (e.g. `301` or `302`) to another host is
+    /// refused with [`Error::InvalidRequest`] instead of being followed
+    /// silently.
+    ///
+    /// # Arguments
+    ///
+    /// - `restrict`: `true` to refuse redirects to another host
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arkiv::{Downloader, Result};
+    ///
+    /// pub fn main() -> Result<()> {
+    ///     let url = "https://github.com/meuter/arkiv-rs/raw/main/tests/sample/sample.zip";
+    ///     let mut archive = Downloader::new()
+    ///         .url(url)
+    ///         .to_temp()
+    ///         .with_restrict_redirects_to_same_host(true)
+    ///         .download()?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn with_restrict_redirects_to_same_host(mut self, restrict: bool) -> Self {
+        self.options.same_host_redirects = restrict;
+        self
+    }
+
+    /// Authenticates the request with a `Bearer` token read from an
+    /// environment variable (e.g. `GITHUB_TOKEN`), which keeps the token out
+    /// of the source code. The variable is only read when the archive is
+    /// downloaded, and [`Error::MissingEnvVar`] is returned if it is not set.
+    ///
+    /// # Arguments
+    ///
+    /// - `env_var`: the name of the environment variable holding the token
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arkiv::{Downloader, Result};
+    ///
+    /// pub fn main() -> Result<()> {
+    ///     let url = "https://github.com/meuter/arkiv-rs/raw/main/tests/sample/sample.zip";
+    ///     let mut archive = Downloader::new()
+    ///         .url(url)
+    ///         .to_temp()
+    ///         .with_bearer_token_from_env("GITHUB_TOKEN")
+    ///         .download()?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn with_bearer_token_from_env(mut self, env_var: &str) -> Self {
+        self.options.bearer_token_env = Some(env_var.to_string());
+        self
+    }
+
+    /// Retries the request up to `count` times when it fails with a
+    /// transient error, i.e. a network error or a `5xx` or `429` status
+    /// (no retry by default). The delay between the attempts starts at the
+    /// [`retry_backoff`](Self::retry_backoff) and doubles after each attempt.
+    ///
+    /// Only the request is retried: a download interrupted while receiving
+    /// the archive fails right away, so the progress callback always starts
+    /// from zero with the response that is eventually downloaded.
+    ///
+    /// # Arguments
+    ///
+    /// - `count`: the maximum number of retries
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arkiv::{Downloader, Result};
+    /// use std::time::Duration;
+    ///
+    /// pub fn main() -> Result<()> {
+    ///     let url = "https://github.com/meuter/arkiv-rs/raw/main/tests/sample/sample.zip";
+    ///     let mut archive = Downloader::new()
+    ///         .url(url)
+    ///         .to_temp()
+    ///         .retries(3)
+    ///         .retry_backoff(Duration::from_secs(1))
+    ///         .download()?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn retries(mut self, count: u32) -> Self {
+        self.options.retries = count;
+        self
+    }
+
+    /// Sets the delay before the first retry (500ms by default), see
+    /// [`retries`](Self::retries).
+    ///
+    /// # Arguments
+    ///
+    /// - `backoff`: the delay before the first retry
+    pub fn retry_backoff(mut self, backoff: Duration) -> Self {
+        self.options.retry_backoff = backoff;
+        self
+    }
+
+    fn complete(&self, storage: Storage) -> Result<Archive> {
+        let archive = Archive::new(storage)?;
+        if let Some(on_complete) = &self.options.on_complete {
+            on_complete(&archive);
         }
+        Ok(archive)
     }
 }
 
@@ -181,15 +427,243 @@ impl<O> Downloader<UrlProvided, DestProvided, O> {
     }
 }
 
+impl<O> Downloader<UrlProvided, DestPersistentTempDir, O> {
+    /// downloads the archive to a new temporary directory using `download`,
+    /// the directory being removed unless the archive is downloaded and
+    /// opened successfully
+    fn persist(
+        self,
+        download: impl FnOnce(Downloader<UrlProvided, DestProvided, O>) -> Result<Archive>,
+    ) -> Result<(Archive, PathBuf)> {
+        let temp = tempfile::tempdir()?;
+        let downloader = Downloader {
+            url: self.url,
+            dest: DestProvided::Dir(temp.path().to_path_buf()),
+            on_progress: self.on_progress,
+            options: self.options,
+        };
+        let archive = download(downloader)?;
+        // NOTE: `into_path` has been renamed `keep` in recent versions of tempfile
+        #[allow(deprecated)]
+        let _ = temp.into_path();
+        let path = archive.path().into_owned();
+        Ok((archive, path))
+    }
+}
+
 impl<D, O> Downloader<UrlProvided, D, O> {
+    fn agent(&self) -> Result<Agent> {
+        let mut builder = AgentBuilder::new();
+        if self.options.system_proxy {
+            if let Some(proxy) = system_proxy(&self.url.0)? {
+                builder = builder.proxy(proxy);
+            }
+        }
+        if self.options.same_host_redirects {
+            // redirects are followed by `get` to check their host
+            builder = builder.redirects(0);
+        }
+        Ok(builder.build())
+    }
+
     fn get(&self) -> Result<Response> {
-        let response = ureq::get(&self.url.0)
-            .call()
-            .map_err(|err| Error::InvalidRequest(err.to_string()))?;
-        Ok(response)
+        let agent = self.agent()?;
+        let authorization = match &self.options.bearer_token_env {
+            Some(name) => {
+                let token = env::var(name).map_err(|_| Error::MissingEnvVar(name.clone()))?;
+                Some(format!("Bearer {token}"))
+            }
+            None => None,
+        };
+        let call = |url: &str| {
+            let mut backoff = self.options.retry_backoff;
+            let mut retries = self.options.retries;
+            loop {
+                let mut request = agent.get(url);
+                if let Some(authorization) = &authorization {
+                    request = request.set("Authorization", authorization);
+                }
+                match request.call() {
+                    Err(err) if retries > 0 && is_transient(&err) => {
+                        thread::sleep(backoff);
+                        backoff = backoff.saturating_mul(2);
+                        retries -= 1;
+                    }
+                    result => return result.map_err(|err| Error::InvalidRequest(err.to_string())),
+                }
+            }
+        };
+        if !self.options.same_host_redirects {
+            return call(&self.url.0);
+        }
+
+        let expected_host = host(&self.url.0)?;
+        let mut url = self.url.0.clone();
+        for _ in 0..=MAX_REDIRECTS {
+            let response = call(&url)?;
+            if !(300..400).contains(&response.status()) {
+                return Ok(response);
+            }
+            let location = response.header("location").ok_or(Error::InvalidRequest(
+                "redirect response does not contain 'location' header".to_string(),
+            ))?;
+            url = resolve_location(&url, location);
+            if !expected_host.eq_ignore_ascii_case(&host(&url)?) {
+                Err(Error::InvalidRequest(format!(
+                    "redirect to another host refused: '{url}'"
+                )))?;
+            }
+        }
+        Err(Error::InvalidRequest("too many redirects".to_string()))
+    }
+}
+
+/// Returns `true` if a request failed with an error that may not happen
+/// again, i.e. a network error, a server error or too many requests.
+fn is_transient(err: &ureq::Error) -> bool {
+    match err {
+        ureq::Error::Status(status, _) => *status == 429 || (500..600).contains(status),
+        ureq::Error::Transport(transport) => matches!(
+            transport.kind(),
+            ureq::ErrorKind::Dns | ureq::ErrorKind::ConnectionFailed | ureq::ErrorKind::Io
+        ),
+    }
+}
+
+/// Returns the host of a URL.
+fn host(url: &str) -> Result<String> {
+    let request_url = ureq::get(url)
+        .request_url()
+        .map_err(|_| Error::InvalidUrl(url.to_string()))?;
+    Ok(request_url.host().to_string())
+}
+
+/// Resolves the `location` of a redirect relatively to the URL that was requested.
+fn resolve_location(url: &str, location: &str) -> String {
+    let url = url.split(['?', '#']).next().unwrap_or(url);
+    let scheme_end = url.find("://").map_or(0, |pos| pos + 3);
+    let path_start = url[scheme_end..]
+        .find('/')
+        .map_or(url.len(), |pos| scheme_end + pos);
+    if location.contains("://") {
+        location.to_string()
+    } else if location.starts_with("//") {
+        format!("{}{}", &url[..scheme_end.saturating_sub(2)], location)
+    } else if location.starts_with('/') {
+        format!("{}{}", &url[..path_start], location)
+    } else {
+        let dir_end = url[path_start..]
+            .rfind('/')
+            .map_or(url.len(), |pos| path_start + pos + 1);
+        match &url[..dir_end] {
+            dir if dir.ends_with('/') => format!("{dir}{location}"),
+            dir => format!("{dir}/{location}"),
+        }
     }
 }
 
+/// Returns the value of an environment variable, looked up in upper then lower case.
+fn env_var(name: &str) -> Option<String> {
+    env::var(name)
+        .or_else(|_| env::var(name.to_lowercase()))
+        .ok()
+        .filter(|value| !value.is_empty())
+}
+
+/// Returns the proxy configured in the environment for a given URL, if any.
+fn system_proxy(url: &str) -> Result<Option<Proxy>> {
+    let request_url = ureq::get(url)
+        .request_url()
+        .map_err(|_| Error::InvalidUrl(url.to_string()))?;
+    let proxy = match request_url.scheme() {
+        "https" => env_var("HTTPS_PROXY"),
+        _ => env_var("HTTP_PROXY"),
+    }
+    .or_else(|| env_var("ALL_PROXY"));
+    let no_proxy = env_var("NO_PROXY").unwrap_or_default();
+
+    match proxy {
+        Some(proxy) if !bypass_proxy(request_url.host(), &no_proxy) => {
+            let proxy = Proxy::new(proxy).map_err(|err| Error::InvalidRequest(err.to_string()))?;
+            Ok(Some(proxy))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Returns `true` if a host matches one of the comma separated entries of `NO_PROXY`.
+fn bypass_proxy(host: &str, no_proxy: &str) -> bool {
+    let host = host
+        .trim_matches(|c| c == '[' || c == ']')
+        .to_ascii_lowercase();
+    let ip = host.parse::<IpAddr>().ok();
+    no_proxy
+        .split(',')
+        .map(|entry| entry.trim().trim_matches(|c| c == '[' || c == ']'))
+        .filter(|entry| !entry.is_empty())
+        .any(|entry| match (ip, entry.split_once('/')) {
+            _ if entry == "*" => true,
+            (Some(ip), Some((network, prefix_len))) => {
+                match (ip, network.parse::<IpAddr>(), prefix_len.parse::<u32>()) {
+                    (IpAddr::V4(ip), Ok(IpAddr::V4(network)), Ok(prefix_len))
+                        if prefix_len <= 32 =>
+                    {
+                        let mask = u32::MAX.checked_shl(32 - prefix_len).unwrap_or(0);
+                        u32::from(ip) & mask == u32::from(network) & mask
+                    }
+                    (IpAddr::V6(ip), Ok(IpAddr::V6(network)), Ok(prefix_len))
+                        if prefix_len <= 128 =>
+                    {
+                        let mask = u128::MAX.checked_shl(128 - prefix_len).unwrap_or(0);
+                        u128::from(ip) & mask == u128::from(network) & mask
+                    }
+                    _ => false,
+                }
+            }
+            _ => {
+                let domain = entry.trim_start_matches('*').trim_start_matches('.');
+                let domain = domain.to_ascii_lowercase();
+                host == domain || host.ends_with(&format!(".{domain}"))
+            }
+        })
+}
+
+/// Reader keeping track of the number of bytes read from the underlying reader.
+struct CountingReader<R> {
+    inner: R,
+    count: Rc<Cell<u64>>,
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let len = self.inner.read(buf)?;
+        self.count.set(self.count.get() + len as u64);
+        Ok(len)
+    }
+}
+
+/// Decodes the body of a response according to its content encoding (e.g. a
+/// gzip compressed response body), so that only the raw archive is stored.
+fn decode<'a>(content_encoding: Option<&str>, body: impl Read + 'a) -> Result<Box<dyn Read + 'a>> {
+    match content_encoding.map(str::trim) {
+        None | Some("identity") => Ok(Box::new(body)),
+        Some("gzip") | Some("x-gzip") => Ok(Box::new(GzDecoder::new(body))),
+        Some(content_encoding) => Err(Error::InvalidRequest(format!(
+            "unsupported content encoding '{content_encoding}'"
+        ))),
+    }
+}
+
+/// Requests an archive and returns the decoded body of the response, to be
+/// read as it is received.
+pub(crate) fn stream(url: &str) -> Result<Box<dyn Read>> {
+    let response = ureq::get(url)
+        .call()
+        .map_err(|err| Error::InvalidRequest(err.to_string()))?;
+    let content_encoding = response.header("content-encoding").map(str::to_string);
+    decode(content_encoding.as_deref(), response.into_reader())
+}
+
 impl Downloader<UrlProvided, DestProvided, OnProgressNotProvided> {
     /// Downloads the archive without progress report.
     ///
@@ -216,12 +690,13 @@ impl Downloader<UrlProvided, DestProvided, OnProgressNotProvided> {
         let response = self.get()?;
         let storage = self.storage()?;
 
-        let mut source = response.into_reader();
+        let content_encoding = response.header("content-encoding").map(str::to_string);
+        let mut source = decode(content_encoding.as_deref(), response.into_reader())?;
         let mut dest = storage.create()?;
 
         std::io::copy(&mut source, &mut dest)?;
 
-        Archive::new(storage)
+        self.complete(storage)
     }
 }
 
@@ -274,21 +749,110 @@ impl<F: FnMut(u64, u64)> Downloader<UrlProvided, DestProvided, OnProgressProvide
                 ))
             })?;
         let storage = self.storage()?;
-        let mut source = response.into_reader();
+
+        // progress is reported in bytes received, which `content-length` refers to
+        let received = Rc::new(Cell::new(0));
+        let content_encoding = response.header("content-encoding").map(str::to_string);
+        let body = CountingReader {
+            inner: response.into_reader(),
+            count: received.clone(),
+        };
+        let mut source = decode(content_encoding.as_deref(), body)?;
         let mut dest = storage.create()?;
 
         let mut buf = [0; 16384];
-        let mut written = 0;
         loop {
-            self.on_progress.0(written as u64, content_length);
+            self.on_progress.0(received.get(), content_length);
             let bytes_read = match source.read(&mut buf) {
-                Ok(0) => return Archive::new(storage),
+                Ok(0) => return self.complete(storage),
                 Ok(len) => len,
                 Err(ref e) if e.kind() == ErrorKind::Interrupted => continue,
                 Err(e) => return Err(e.into()),
             };
             dest.write_all(&buf[..bytes_read])?;
-            written += bytes_read;
         }
     }
 }
+
+impl Downloader<UrlProvided, DestPersistentTempDir, OnProgressNotProvided> {
+    /// Downloads the archive to a persistent temporary directory without
+    /// progress report.
+    ///
+    /// Returns the opened [`Archive`] along with the path of the downloaded
+    /// file. This file is not removed when the [`Archive`] is dropped, it is
+    /// up to the caller to move it or delete it.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arkiv::{Downloader, Result};
+    ///
+    /// pub fn main() -> Result<()> {
+    ///     let url = "https://github.com/meuter/arkiv-rs/raw/main/tests/sample/sample.zip";
+    ///     let (archive, path) = Downloader::new()
+    ///         .url(url)
+    ///         .to_temp_persistent()
+    ///         .download()?;
+    ///
+    ///     drop(archive);
+    ///     assert!(path.exists());
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn download(self) -> Result<(Archive, PathBuf)> {
+        self.persist(|downloader| downloader.download())
+    }
+}
+
+impl<F: FnMut(u64, u64)> Downloader<UrlProvided, DestPersistentTempDir, OnProgressProvided<F>> {
+    /// Downloads the archive to a persistent temporary directory and reports
+    /// on progress.
+    ///
+    /// Returns the opened [`Archive`] along with the path of the downloaded
+    /// file. This file is not removed when the [`Archive`] is dropped, it is
+    /// up to the caller to move it or delete it.
+    pub fn download(self) -> Result<(Archive, PathBuf)> {
+        self.persist(|downloader| downloader.download())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn assert_send<T: Send>() {}
+
+    #[test]
+    fn downloader_is_send() {
+        assert_send::<Downloader<UrlMissing, DestMissing, OnProgressNotProvided>>();
+        assert_send::<Downloader<UrlProvided, DestProvided, OnProgressNotProvided>>();
+        assert_send::<Downloader<UrlProvided, DestPersistentTempDir, OnProgressNotProvided>>();
+    }
+
+    #[test]
+    fn bypass_proxy_matches_no_proxy_entries() {
+        assert!(bypass_proxy("example.com", "*"));
+        assert!(bypass_proxy("example.com", "example.com"));
+        assert!(bypass_proxy("www.Example.com", ".example.com"));
+        assert!(bypass_proxy("www.example.com", "*.example.com"));
+        assert!(!bypass_proxy("notexample.com", "example.com"));
+        assert!(!bypass_proxy("example.org", "example.com,"));
+        assert!(bypass_proxy("127.0.0.1", "example.com, 127.0.0.0/8"));
+        assert!(!bypass_proxy("10.0.0.1", "127.0.0.0/8"));
+        assert!(bypass_proxy("10.1.2.3", "0.0.0.0/0"));
+        assert!(bypass_proxy("[::1]", "::1"));
+        assert!(bypass_proxy("[::1]", "[::1]"));
+        assert!(bypass_proxy("[::1]", "::1/128"));
+        assert!(bypass_proxy("[fe80::1]", "fe80::/10"));
+        assert!(!bypass_proxy("[::1]", "127.0.0.0/8"));
+        assert!(!bypass_proxy("127.0.0.1", "::/0"));
+    }
+
+    #[test]
+    fn only_network_errors_are_transient() {
+        let call = |url: &str| ureq::get(url).call().unwrap_err();
+        assert!(is_transient(&call("http://127.0.0.1:1/sample.zip")));
+        assert!(!is_transient(&call("ftp://127.0.0.1/sample.zip")));
+        assert!(!is_transient(&call("not a url")));
+    }
+}
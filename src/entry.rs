@@ -1,28 +1,63 @@
-use std::path::{Path, PathBuf};
+use std::{
+    path::{Component, Path, PathBuf},
+    time::SystemTime,
+};
 
 use crate::Result;
 
 /// The type of an entry within an archive.
 #[derive(Debug, PartialEq, Eq, Clone)]
-pub(crate) enum EntryType {
+pub enum EntryType {
     /// The entry is a directory
     Directory,
 
     /// The entry is a file
     File,
 
-    /// The entry is neither a directory nor a regular file
-    #[cfg(feature = "tar")]
+    /// The entry is a symbolic link
+    Symlink {
+        /// the path the link points to, as stored in the archive
+        target: PathBuf,
+    },
+
+    /// The entry is a hard link to another entry of the archive
+    Hardlink {
+        /// the path of the entry the link points to, relative to the root
+        /// of the archive
+        target: PathBuf,
+    },
+
+    /// The entry is neither a directory, a regular file nor a link (e.g. a
+    /// device or a FIFO)
     Other,
 }
 
+/// Returns the path of an entry relative to the directory it is unpacked
+/// into, i.e. without the leading `/` of archives storing absolute paths,
+/// which would otherwise be unpacked outside of the destination directory.
+pub(crate) fn unpack_path(path: &Path) -> &Path {
+    let mut components = path.components();
+    while let Some(Component::RootDir | Component::Prefix(_)) = components.clone().next() {
+        components.next();
+    }
+    components.as_path()
+}
+
 /// A descriptor of one entry in an archive.
 #[derive(Debug, Clone)]
 pub struct Entry {
     pub(crate) index: usize,
     pub(crate) path: PathBuf,
     pub(crate) size: u64,
+    pub(crate) compressed_size: Option<u64>,
     pub(crate) entry_type: EntryType,
+    pub(crate) creator_os: Option<u8>,
+    pub(crate) unix_mode: Option<u32>,
+    pub(crate) modified: Option<SystemTime>,
+    pub(crate) uid: Option<u64>,
+    pub(crate) gid: Option<u64>,
+    pub(crate) uname: Option<String>,
+    pub(crate) gname: Option<String>,
 }
 
 impl Entry {
@@ -36,11 +71,33 @@ impl Entry {
         &self.path
     }
 
-    /// Returns the size of the entry
+    /// Returns the uncompressed size of the entry. For tar archives, sizes
+    /// stored in PAX extended headers (e.g. for entries larger than 8 GiB)
+    /// take precedence over the size stored in the ustar header.
     pub fn size(&self) -> u64 {
         self.size
     }
 
+    /// Returns the compressed size of the entry for formats compressing each
+    /// entry individually, i.e. zip archives. Returns `None` for the other
+    /// formats (e.g. tar archives, which are compressed as a whole).
+    pub fn compressed_size(&self) -> Option<u64> {
+        self.compressed_size
+    }
+
+    /// Returns the ratio between the compressed size and the uncompressed
+    /// size of the entry, e.g. `0.25` if the entry was compressed to a
+    /// quarter of its size. Returns `None` if the compressed size is not
+    /// known or if either size is zero.
+    pub fn compression_ratio(&self) -> Option<f64> {
+        match self.compressed_size {
+            Some(compressed_size) if compressed_size != 0 && self.size != 0 => {
+                Some(compressed_size as f64 / self.size as f64)
+            }
+            _ => None,
+        }
+    }
+
     /// Returns `true` if the entry corresponds to a directory
     pub fn is_dir(&self) -> bool {
         self.entry_type == EntryType::Directory
@@ -50,6 +107,112 @@ impl Entry {
     pub fn is_file(&self) -> bool {
         self.entry_type == EntryType::File
     }
+
+    /// Returns `true` if the entry corresponds to a symbolic link
+    pub fn is_symlink(&self) -> bool {
+        matches!(self.entry_type, EntryType::Symlink { .. })
+    }
+
+    /// Returns the target of the entry if it is a symbolic link, i.e. the
+    /// path it points to, relative to the directory containing the link.
+    /// Returns `None` for the other types of entries.
+    pub fn symlink_target(&self) -> Option<&Path> {
+        match &self.entry_type {
+            EntryType::Symlink { target } => Some(target),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if the entry corresponds to a hard link
+    pub fn is_hardlink(&self) -> bool {
+        matches!(self.entry_type, EntryType::Hardlink { .. })
+    }
+
+    /// Returns the target of the entry if it is a hard link, i.e. the path
+    /// of the entry it points to, relative to the root of the archive.
+    /// Returns `None` for the other types of entries.
+    pub fn hardlink_target(&self) -> Option<&Path> {
+        match &self.entry_type {
+            EntryType::Hardlink { target } => Some(target),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if the entry is neither a directory, a regular file
+    /// nor a link, e.g. a device or a FIFO. Such entries are skipped when
+    /// the archive is unpacked.
+    pub fn is_other(&self) -> bool {
+        self.entry_type == EntryType::Other
+    }
+
+    /// Returns `true` if the entry is a file with at least one of its
+    /// executable bits set in its unix permissions. Returns `false` if the
+    /// archive does not store unix permissions for this entry (e.g. zip
+    /// archives created on Windows).
+    pub fn is_executable(&self) -> bool {
+        self.is_file() && self.unix_mode.is_some_and(|mode| mode & 0o111 != 0)
+    }
+
+    /// Returns the unix permission bits of the entry (e.g. `0o755`), without
+    /// the file type bits stored by some archive formats.
+    ///
+    /// Returns `None` if the archive does not store unix permissions for
+    /// this entry (e.g. zip archives created on Windows).
+    pub fn unix_mode(&self) -> Option<u32> {
+        self.unix_mode.map(|mode| mode & 0o7777)
+    }
+
+    /// Returns the raw host system that created the entry, as stored in
+    /// the "version made by" field of zip archives, e.g. `0` for MS-DOS
+    /// and Windows, `3` for Unix or `11` for NTFS. Paths of entries created
+    /// on MS-DOS or Windows may require some normalization.
+    ///
+    /// Returns `None` for archive formats that do not store this information.
+    pub fn creator_os(&self) -> Option<u8> {
+        self.creator_os
+    }
+
+    /// Returns the last modification time of the entry, which is restored
+    /// on the files when they are unpacked.
+    ///
+    /// Returns `None` if the archive does not store it or if it is invalid
+    /// (e.g. a zeroed MS-DOS timestamp). The timestamps of zip archives do
+    /// not carry a time zone and are interpreted as UTC.
+    pub fn last_modified(&self) -> Option<SystemTime> {
+        self.modified
+    }
+
+    /// Returns the numeric id of the user owning the entry.
+    ///
+    /// Returns `None` if the archive does not store it (e.g. zip archives)
+    /// or if it is invalid.
+    pub fn uid(&self) -> Option<u64> {
+        self.uid
+    }
+
+    /// Returns the numeric id of the group owning the entry.
+    ///
+    /// Returns `None` if the archive does not store it (e.g. zip archives)
+    /// or if it is invalid.
+    pub fn gid(&self) -> Option<u64> {
+        self.gid
+    }
+
+    /// Returns the name of the user owning the entry, e.g. `root`.
+    ///
+    /// Returns `None` if the archive does not store it (e.g. zip archives
+    /// or tar archives in the old v7 format) or if it is empty or invalid.
+    pub fn uname(&self) -> Option<&str> {
+        self.uname.as_deref()
+    }
+
+    /// Returns the name of the group owning the entry, e.g. `wheel`.
+    ///
+    /// Returns `None` if the archive does not store it (e.g. zip archives
+    /// or tar archives in the old v7 format) or if it is empty or invalid.
+    pub fn gname(&self) -> Option<&str> {
+        self.gname.as_deref()
+    }
 }
 
 /// An iterator over the entries of the archive
@@ -61,6 +224,51 @@ pub struct FindEntries<'a, P: FnMut(&Entry) -> bool> {
     pub(crate) inner: Entries<'a>,
 }
 
+impl<'a, P: FnMut(&Entry) -> bool> FindEntries<'a, P> {
+    /// Only keeps the entries that also match another boolean predicate.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arkiv::{Archive, Entry, Result};
+    ///
+    /// fn main() -> Result<()> {
+    ///     let mut archive = Archive::open("path/to/archive.tgz")?;
+    ///     let small_files_and_dirs = archive
+    ///         .find(Entry::is_file)?
+    ///         .and(|entry| entry.size() < 1024)
+    ///         .or(Entry::is_dir);
+    ///     for entry in small_files_and_dirs {
+    ///         println!("{}", entry?.path().display());
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn and<Q: FnMut(&Entry) -> bool>(
+        self,
+        mut other: Q,
+    ) -> FindEntries<'a, impl FnMut(&Entry) -> bool> {
+        let mut predicate = self.predicate;
+        FindEntries {
+            predicate: move |entry: &Entry| predicate(entry) && other(entry),
+            inner: self.inner,
+        }
+    }
+
+    /// Also keeps the entries that match another boolean predicate. See
+    /// [`and`](Self::and) for an example.
+    pub fn or<Q: FnMut(&Entry) -> bool>(
+        self,
+        mut other: Q,
+    ) -> FindEntries<'a, impl FnMut(&Entry) -> bool> {
+        let mut predicate = self.predicate;
+        FindEntries {
+            predicate: move |entry: &Entry| predicate(entry) || other(entry),
+            inner: self.inner,
+        }
+    }
+}
+
 impl<'a, P: FnMut(&Entry) -> bool> Iterator for FindEntries<'a, P> {
     type Item = Result<Entry>;
 
@@ -1,14 +1,20 @@
 use std::path::Path;
 
 /// Available archive file formats.
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
 pub enum Format {
-    /// Compressed zip archive
+    /// Compressed zip archive (including EPUB e-books)
     Zip,
 
+    /// Chrome extension, i.e. a zip archive prefixed with a `Cr24` header
+    Crx,
+
     /// Uncompressed tar archive
     Tar,
 
+    /// Debian package, i.e. an uncompressed ar archive
+    Deb,
+
     /// File comressed with Gzip
     Gzip,
 
@@ -33,6 +39,23 @@ pub enum Format {
     /// Tar archive compressed with Zstd
     TarZstd,
 
+    /// Tar archive compressed with Lzip
+    TarLzip,
+
+    /// Compressed 7z archive
+    SevenZ,
+
+    /// RPM package, i.e. a cpio archive wrapped in rpm headers
+    Rpm,
+
+    /// SquashFS filesystem image (e.g. Snapcraft packages), recognized but
+    /// not supported
+    Squashfs,
+
+    /// ISO 9660 filesystem image (e.g. install media), recognized but not
+    /// supported
+    Iso,
+
     /// unknown archive format.
     Unknown,
 }
@@ -43,6 +66,11 @@ pub enum Format {
 )]
 pub type ArchiveKind = Format;
 
+/// number of bytes needed by [`Format::infer_from_magic_bytes`] to recognize
+/// any format, the ISO 9660 magic being located at offset 32769, after the
+/// system area of the image
+pub(crate) const MAGIC_BYTES_LEN: usize = 32774;
+
 macro_rules! match_ext {
     ($path: expr, $ext: expr) => {
         match $path.extension() {
@@ -82,10 +110,14 @@ impl Format {
     pub fn infer_from_file_extension(path: impl AsRef<Path>) -> Self {
         let path = path.as_ref();
 
-        if match_ext!(path, "zip") {
+        if match_ext!(path, "zip") || match_ext!(path, "epub") {
             Format::Zip
-        } else if match_ext!(path, "tar") {
+        } else if match_ext!(path, "crx") {
+            Format::Crx
+        } else if match_ext!(path, "tar") || match_ext!(path, "gem") {
             Format::Tar
+        } else if match_ext!(path, "deb") {
+            Format::Deb
         } else if match_ext!(path, "tgz") || match_ext!(path, "tar", "gz") {
             Format::TarGzip
         } else if match_ext!(path, "tar", "xz") {
@@ -94,6 +126,16 @@ impl Format {
             Format::TarBzip2
         } else if match_ext!(path, "tar", "zstd") || match_ext!(path, "tar", "zst") {
             Format::TarZstd
+        } else if match_ext!(path, "tar", "lz") {
+            Format::TarLzip
+        } else if match_ext!(path, "7z") {
+            Format::SevenZ
+        } else if match_ext!(path, "rpm") {
+            Format::Rpm
+        } else if match_ext!(path, "snap") || match_ext!(path, "squashfs") {
+            Format::Squashfs
+        } else if match_ext!(path, "iso") {
+            Format::Iso
         } else if match_ext!(path, "gz") {
             Format::Gzip
         } else if match_ext!(path, "xz") {
@@ -107,6 +149,98 @@ impl Format {
         }
     }
 
+    /// Infers the archive format from the magic bytes found at the start of
+    /// its contents, e.g. when the file extension is wrong or missing. The
+    /// first 262 bytes are needed to recognize tar archives, the other formats
+    /// are recognized from their first few bytes.
+    ///
+    /// Compressed tar archives cannot be told apart from single compressed
+    /// files without decompressing them, so only their compression is
+    /// detected (e.g. [`Format::Gzip`] rather than [`Format::TarGzip`]).
+    ///
+    /// # Arguments
+    ///
+    /// - `data`: the first bytes of a candidate archive file
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use arkiv::Format;
+    ///
+    /// let format = Format::infer_from_magic_bytes(b"PK\x03\x04\x14\x00");
+    /// assert_eq!(format, Format::Zip);
+    /// ```
+    pub fn infer_from_magic_bytes(data: &[u8]) -> Self {
+        if data.starts_with(b"PK\x03\x04") || data.starts_with(b"PK\x05\x06") {
+            Format::Zip
+        } else if data.starts_with(b"Cr24") {
+            Format::Crx
+        } else if data.starts_with(b"7z\xbc\xaf\x27\x1c") {
+            Format::SevenZ
+        } else if data.starts_with(b"\xed\xab\xee\xdb") {
+            Format::Rpm
+        } else if data.starts_with(b"\x1f\x8b") {
+            Format::Gzip
+        } else if data.starts_with(b"BZh") {
+            Format::Bzip2
+        } else if data.starts_with(b"\xfd7zXZ\x00") {
+            Format::Xz2
+        } else if data.starts_with(b"\x28\xb5\x2f\xfd") {
+            Format::Zstd
+        } else if data.starts_with(b"hsqs") {
+            Format::Squashfs
+        } else if data.get(257..262) == Some(b"ustar") {
+            Format::Tar
+        } else if data.get(32769..MAGIC_BYTES_LEN) == Some(b"CD001") {
+            Format::Iso
+        } else {
+            Format::Unknown
+        }
+    }
+
+    /// human readable name of the format, see [`Archive::format_display`](crate::Archive::format_display)
+    pub(crate) fn display_name(&self) -> &'static str {
+        match self {
+            Format::Zip => "ZIP",
+            Format::Crx => "CRX",
+            Format::Tar => "TAR",
+            Format::Deb => "DEB",
+            Format::Gzip => "GZip",
+            Format::Zstd => "Zstd",
+            Format::Bzip2 => "BZip2",
+            Format::Xz2 => "XZ",
+            Format::TarGzip => "TAR+GZip",
+            Format::TarBzip2 => "TAR+BZip2",
+            Format::TarXz2 => "TAR+XZ",
+            Format::TarZstd => "TAR+Zstd",
+            Format::TarLzip => "TAR+LZip",
+            Format::SevenZ => "7Z",
+            Format::Rpm => "RPM",
+            Format::Squashfs => "SquashFS",
+            Format::Iso => "ISO",
+            Format::Unknown => "unknown",
+        }
+    }
+
+    /// Returns `true` if the magic bytes at the start of a file match the
+    /// format, the compressed tar archives being recognized by their
+    /// compression only. Tar archives without magic bytes (i.e. pre-POSIX
+    /// archives) are accepted as well.
+    pub(crate) fn matches_magic_bytes(&self, data: &[u8]) -> bool {
+        let inferred = Format::infer_from_magic_bytes(data);
+        match self {
+            Format::Tar => matches!(inferred, Format::Tar | Format::Unknown),
+            Format::Deb => data.starts_with(b"!<arch>\n"),
+            Format::TarGzip => inferred == Format::Gzip,
+            Format::TarBzip2 => inferred == Format::Bzip2,
+            Format::TarXz2 => inferred == Format::Xz2,
+            Format::TarZstd => inferred == Format::Zstd,
+            Format::TarLzip => data.starts_with(b"LZIP"),
+            Format::Unknown => false,
+            _ => inferred == *self,
+        }
+    }
+
     /// Returns `true` if a the format is compressed
     ///
     /// Example
@@ -120,7 +254,7 @@ impl Format {
     /// assert_eq!(Format::Zip.is_compressed(), true);
     /// ```
     pub fn is_compressed(&self) -> bool {
-        !matches!(self, Format::Tar)
+        !matches!(self, Format::Tar | Format::Deb)
     }
 
     /// Returns `true` if the format is an archive (as opposed
@@ -138,7 +272,9 @@ impl Format {
     pub fn is_archive(&self) -> bool {
         match self {
             Format::Zip => true,
+            Format::Crx => true,
             Format::Tar => true,
+            Format::Deb => true,
             Format::Gzip => false,
             Format::Zstd => false,
             Format::Bzip2 => false,
@@ -147,11 +283,95 @@ impl Format {
             Format::TarBzip2 => true,
             Format::TarXz2 => true,
             Format::TarZstd => true,
+            Format::TarLzip => true,
+            Format::SevenZ => true,
+            Format::Rpm => true,
+            Format::Squashfs => true,
+            Format::Iso => true,
+            Format::Unknown => false,
+        }
+    }
+
+    /// Returns `true` if the format is a tar archive, possibly compressed.
+    pub(crate) fn is_tar(&self) -> bool {
+        matches!(
+            self,
+            Format::Tar
+                | Format::TarGzip
+                | Format::TarBzip2
+                | Format::TarXz2
+                | Format::TarZstd
+                | Format::TarLzip
+        )
+    }
+
+    /// Returns `true` if the format can be opened with the features
+    /// enabled in the current build.
+    ///
+    /// #Example
+    /// ```
+    /// use arkiv::Format;
+    ///
+    /// assert_eq!(Format::Zip.is_supported(), cfg!(feature = "zip"));
+    /// assert_eq!(Format::Unknown.is_supported(), false);
+    /// ```
+    pub fn is_supported(&self) -> bool {
+        match self {
+            Format::Zip => cfg!(feature = "zip"),
+            Format::Crx => cfg!(feature = "crx"),
+            Format::Tar => cfg!(feature = "tar"),
+            Format::Deb => cfg!(feature = "deb"),
+            Format::Gzip => cfg!(feature = "gzip"),
+            Format::Zstd => cfg!(feature = "zstd"),
+            Format::Bzip2 => cfg!(feature = "bzip2"),
+            Format::Xz2 => cfg!(feature = "xz"),
+            Format::TarGzip => cfg!(all(feature = "tar", feature = "gzip")),
+            Format::TarBzip2 => cfg!(all(feature = "tar", feature = "bzip2")),
+            Format::TarXz2 => cfg!(all(feature = "tar", feature = "xz")),
+            Format::TarZstd => cfg!(all(feature = "tar", feature = "zstd")),
+            Format::TarLzip => cfg!(all(feature = "tar", feature = "lzip")),
+            Format::SevenZ => cfg!(feature = "sevenz"),
+            Format::Rpm => cfg!(feature = "rpm"),
+            Format::Squashfs => false,
+            Format::Iso => false,
             Format::Unknown => false,
         }
     }
 }
 
+/// Returns the list of formats that can be opened with the features
+/// enabled in the current build.
+///
+/// # Example
+///
+/// ```
+/// for format in arkiv::supported_formats() {
+///     println!("{format:?}");
+/// }
+/// ```
+pub fn supported_formats() -> Vec<Format> {
+    [
+        Format::Zip,
+        Format::Crx,
+        Format::Tar,
+        Format::Deb,
+        Format::Gzip,
+        Format::Zstd,
+        Format::Bzip2,
+        Format::Xz2,
+        Format::TarGzip,
+        Format::TarBzip2,
+        Format::TarXz2,
+        Format::TarZstd,
+        Format::TarLzip,
+        Format::SevenZ,
+        Format::Rpm,
+    ]
+    .into_iter()
+    .filter(Format::is_supported)
+    .collect()
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -165,8 +385,13 @@ mod test {
         }
         assert_ext!("sample.zip", Format::Zip);
         assert_ext!("sample.Zip", Format::Zip);
+        assert_ext!("sample.epub", Format::Zip);
+        assert_ext!("sample.EPUB", Format::Zip);
+        assert_ext!("sample.crx", Format::Crx);
         assert_ext!("sample.tar", Format::Tar);
         assert_ext!("sample.TAR", Format::Tar);
+        assert_ext!("sample.gem", Format::Tar);
+        assert_ext!("sample.deb", Format::Deb);
         assert_ext!("sample.tar.gz", Format::TarGzip);
         assert_ext!("sample.tAr.gz", Format::TarGzip);
         assert_ext!("sample.tgz", Format::TarGzip);
@@ -174,6 +399,12 @@ mod test {
         assert_ext!("sample.tar.bz2", Format::TarBzip2);
         assert_ext!("sample.tar.zstd", Format::TarZstd);
         assert_ext!("sample.tar.zst", Format::TarZstd);
+        assert_ext!("sample.tar.lz", Format::TarLzip);
+        assert_ext!("sample.7z", Format::SevenZ);
+        assert_ext!("sample.rpm", Format::Rpm);
+        assert_ext!("sample.snap", Format::Squashfs);
+        assert_ext!("sample.squashfs", Format::Squashfs);
+        assert_ext!("sample.iso", Format::Iso);
         assert_ext!("sample.xz", Format::Xz2);
         assert_ext!("sample.bz2", Format::Bzip2);
         assert_ext!("sample.exe", Format::Unknown);
@@ -182,6 +413,102 @@ mod test {
         assert_ext!("sample.txt.zst", Format::Zstd);
     }
 
+    #[test]
+    fn infer_from_file_extension_edge_cases() {
+        macro_rules! assert_ext {
+            ($path: expr, $expected: expr) => {
+                assert_eq!(Format::infer_from_file_extension($path), $expected)
+            };
+        }
+        // no extension at all
+        assert_ext!("", Format::Unknown);
+        assert_ext!("sample", Format::Unknown);
+        assert_ext!("path/to/sample", Format::Unknown);
+        // single extension, in any case
+        assert_ext!("SAMPLE.ZIP", Format::Zip);
+        assert_ext!("sample.Gz", Format::Gzip);
+        assert_ext!("sample.7Z", Format::SevenZ);
+        // double extension, in any case
+        assert_ext!("SAMPLE.TAR.GZ", Format::TarGzip);
+        assert_ext!("sample.Tar.Xz", Format::TarXz2);
+        assert_ext!("sample.tar.BZ2", Format::TarBzip2);
+        // second extension matches but not the first one
+        assert_ext!("sample.gz", Format::Gzip);
+        assert_ext!("sample.zip.xz", Format::Xz2);
+        assert_ext!("sample.tar.7z", Format::SevenZ);
+        // first extension matches but not the second one
+        assert_ext!("sample.tar.bak", Format::Unknown);
+        assert_ext!("sample.tar.gz.part", Format::Unknown);
+        assert_ext!("sample.zip.bak", Format::Unknown);
+        // only the file name is considered, not the parent directories
+        assert_ext!("path/to/sample.tar.gz", Format::TarGzip);
+        assert_ext!("sample.tar/sample.gz", Format::Gzip);
+        assert_ext!("sample.zip/sample", Format::Unknown);
+    }
+
+    #[test]
+    fn display_name() {
+        assert_eq!(Format::Zip.display_name(), "ZIP");
+        assert_eq!(Format::TarGzip.display_name(), "TAR+GZip");
+        assert_eq!(Format::TarZstd.display_name(), "TAR+Zstd");
+        assert_eq!(Format::Gzip.display_name(), "GZip");
+    }
+
+    #[test]
+    fn matches_magic_bytes() {
+        let mut tar = vec![0; MAGIC_BYTES_LEN];
+        tar[257..262].copy_from_slice(b"ustar");
+
+        assert!(Format::Zip.matches_magic_bytes(b"PK\x03\x04"));
+        assert!(!Format::Zip.matches_magic_bytes(&tar));
+        assert!(Format::Tar.matches_magic_bytes(&tar));
+        assert!(Format::Tar.matches_magic_bytes(b"sample/"));
+        assert!(!Format::Tar.matches_magic_bytes(b"PK\x03\x04"));
+        assert!(Format::TarGzip.matches_magic_bytes(b"\x1f\x8b\x08"));
+        assert!(Format::Gzip.matches_magic_bytes(b"\x1f\x8b\x08"));
+        assert!(!Format::TarGzip.matches_magic_bytes(b"BZh9"));
+        assert!(Format::TarBzip2.matches_magic_bytes(b"BZh9"));
+        assert!(Format::TarXz2.matches_magic_bytes(b"\xfd7zXZ\x00"));
+        assert!(Format::TarZstd.matches_magic_bytes(b"\x28\xb5\x2f\xfd"));
+        assert!(Format::TarLzip.matches_magic_bytes(b"LZIP\x01"));
+        assert!(Format::Deb.matches_magic_bytes(b"!<arch>\ndebian-binary"));
+        assert!(!Format::Deb.matches_magic_bytes(&tar));
+        assert!(Format::Crx.matches_magic_bytes(b"Cr24"));
+        assert!(Format::SevenZ.matches_magic_bytes(b"7z\xbc\xaf\x27\x1c"));
+        assert!(Format::Rpm.matches_magic_bytes(b"\xed\xab\xee\xdb"));
+        assert!(!Format::Unknown.matches_magic_bytes(b""));
+    }
+
+    #[test]
+    fn infer_from_magic_bytes() {
+        macro_rules! assert_magic {
+            ($data: expr, $expected: expr) => {
+                assert_eq!(Format::infer_from_magic_bytes($data), $expected)
+            };
+        }
+        let mut tar = vec![0; 512];
+        tar[257..263].copy_from_slice(b"ustar\x00");
+        assert_magic!(b"PK\x03\x04\x14\x00\x00\x00", Format::Zip);
+        assert_magic!(b"PK\x05\x06\x00\x00\x00\x00", Format::Zip);
+        assert_magic!(b"Cr24\x03\x00\x00\x00", Format::Crx);
+        assert_magic!(b"7z\xbc\xaf\x27\x1c\x00\x04", Format::SevenZ);
+        assert_magic!(b"\xed\xab\xee\xdb\x03\x00", Format::Rpm);
+        assert_magic!(b"\x1f\x8b\x08\x00", Format::Gzip);
+        assert_magic!(b"BZh91AY&SY", Format::Bzip2);
+        assert_magic!(b"\xfd7zXZ\x00\x00\x04", Format::Xz2);
+        assert_magic!(b"\x28\xb5\x2f\xfd\x24\x00", Format::Zstd);
+        assert_magic!(b"hsqs\x04\x00\x00\x00", Format::Squashfs);
+        assert_magic!(&tar, Format::Tar);
+        let mut iso = vec![0; MAGIC_BYTES_LEN];
+        iso[32769..].copy_from_slice(b"CD001");
+        assert_magic!(&iso, Format::Iso);
+        // too short or unknown
+        assert_magic!(b"", Format::Unknown);
+        assert_magic!(b"PK", Format::Unknown);
+        assert_magic!(&tar[..260], Format::Unknown);
+        assert_magic!(b"sample\n", Format::Unknown);
+    }
+
     #[test]
     fn is_compressed() {
         macro_rules! assert_ext {
@@ -190,11 +517,16 @@ mod test {
             };
         }
         assert_ext!(Format::Zip, true);
+        assert_ext!(Format::Crx, true);
         assert_ext!(Format::Tar, false);
+        assert_ext!(Format::Deb, false);
         assert_ext!(Format::TarGzip, true);
         assert_ext!(Format::TarXz2, true);
         assert_ext!(Format::TarBzip2, true);
         assert_ext!(Format::TarZstd, true);
+        assert_ext!(Format::TarLzip, true);
+        assert_ext!(Format::SevenZ, true);
+        assert_ext!(Format::Rpm, true);
         assert_ext!(Format::Xz2, true);
         assert_ext!(Format::Bzip2, true);
         assert_ext!(Format::Gzip, true);
@@ -210,15 +542,60 @@ mod test {
             };
         }
         assert_ext!(Format::Zip, true);
+        assert_ext!(Format::Crx, true);
         assert_ext!(Format::Tar, true);
+        assert_ext!(Format::Deb, true);
         assert_ext!(Format::TarGzip, true);
         assert_ext!(Format::TarXz2, true);
         assert_ext!(Format::TarBzip2, true);
         assert_ext!(Format::TarZstd, true);
+        assert_ext!(Format::TarLzip, true);
+        assert_ext!(Format::SevenZ, true);
+        assert_ext!(Format::Rpm, true);
+        assert_ext!(Format::Squashfs, true);
+        assert_ext!(Format::Iso, true);
         assert_ext!(Format::Xz2, false);
         assert_ext!(Format::Bzip2, false);
         assert_ext!(Format::Gzip, false);
         assert_ext!(Format::Zstd, false);
         assert_ext!(Format::Zstd, false);
     }
+
+    #[test]
+    fn supported_formats() {
+        let formats = super::supported_formats();
+        assert_eq!(formats.contains(&Format::Zip), cfg!(feature = "zip"));
+        assert_eq!(formats.contains(&Format::Crx), cfg!(feature = "crx"));
+        assert_eq!(formats.contains(&Format::Tar), cfg!(feature = "tar"));
+        assert_eq!(
+            formats.contains(&Format::TarGzip),
+            cfg!(all(feature = "tar", feature = "gzip"))
+        );
+        assert_eq!(
+            formats.contains(&Format::TarZstd),
+            cfg!(all(feature = "tar", feature = "zstd"))
+        );
+        assert_eq!(formats.contains(&Format::SevenZ), cfg!(feature = "sevenz"));
+        assert_eq!(formats.contains(&Format::Rpm), cfg!(feature = "rpm"));
+        assert_eq!(formats.contains(&Format::Gzip), cfg!(feature = "gzip"));
+        assert!(!formats.contains(&Format::Squashfs));
+        assert!(!formats.contains(&Format::Iso));
+        assert!(!formats.contains(&Format::Unknown));
+        assert!(formats.iter().all(Format::is_supported));
+    }
+
+    #[test]
+    fn hash() {
+        use std::collections::HashMap;
+
+        let mut counts: HashMap<Format, usize> = HashMap::new();
+        for path in ["a.zip", "b.tar.gz", "c.tgz", "d.zip"] {
+            *counts
+                .entry(Format::infer_from_file_extension(path))
+                .or_default() += 1;
+        }
+        assert_eq!(counts[&Format::Zip], 2);
+        assert_eq!(counts[&Format::TarGzip], 2);
+        assert!(!counts.contains_key(&Format::Tar));
+    }
 }
@@ -1,9 +1,12 @@
 #![doc = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/README.md"))]
 
 mod archive;
+mod cursor;
 mod entry;
 mod format;
+mod options;
 mod result;
+mod scan;
 
 #[cfg(feature = "download")]
 mod download;
@@ -14,14 +17,47 @@ mod zip;
 #[cfg(feature = "tar")]
 mod tar;
 
+#[cfg(feature = "sevenz")]
+mod sevenz;
+
+#[cfg(all(feature = "tar", feature = "lzip"))]
+mod lzip;
+
+#[cfg(any(feature = "gzip", feature = "bzip2", feature = "xz", feature = "zstd"))]
+mod compressed;
+
+#[cfg(any(feature = "zip", feature = "tar"))]
+mod builder;
+
+#[cfg(feature = "deb")]
+mod ar;
+
+#[cfg(feature = "deb")]
+mod deb;
+
+#[cfg(feature = "rpm")]
+mod cpio;
+
+#[cfg(feature = "rpm")]
+mod rpm;
+
 pub use archive::Archive;
-pub use entry::{Entries, Entry, FindEntries};
-pub use format::Format;
+pub use cursor::EntryCursor;
+pub use entry::{Entries, Entry, EntryType, FindEntries};
+pub use format::{supported_formats, Format};
+pub use options::{ArchiveOptions, Unmatched};
 pub use result::{Error, Result};
+pub use scan::ArchiveScan;
+
+#[cfg(any(feature = "zip", feature = "tar"))]
+pub use builder::{ArchiveBuilder, CompressionMode};
 
 #[cfg(feature = "download")]
 pub use download::Downloader;
 
+#[cfg(feature = "deb")]
+pub use deb::DebPackage;
+
 /// Available archive file formats.
 #[allow(deprecated)]
 pub use format::ArchiveKind;
@@ -0,0 +1,280 @@
+use std::io::{self, BufRead, BufReader, ErrorKind, Read};
+
+use crc32fast::Hasher;
+use xz2::stream::{Action, Status, Stream};
+
+use crate::Error;
+
+/// size of the header of an lzip member
+const LZIP_HEADER_LEN: usize = 6;
+
+/// size of the trailer of an lzip member
+const LZIP_TRAILER_LEN: usize = 20;
+
+/// size of the header of a legacy `.lzma` stream
+const LZMA_HEADER_LEN: usize = 13;
+
+/// LZMA properties used by every lzip member (lc=3, lp=0, pb=2)
+const LZMA_PROPERTIES: u8 = 0x5d;
+
+/// decoder for lzip compressed data (e.g. `sample.tar.lz`).
+///
+/// An lzip file is a sequence of members, each of them being a raw LZMA
+/// stream with fixed properties, preceded by a short header and followed by
+/// a trailer holding the CRC32 and the size of the data. The header of each
+/// member is translated into the header of the legacy `.lzma` format so that
+/// the stream can be decoded by liblzma, and the trailer is checked once the
+/// stream ends. Data following the last member is ignored, like `lzip` does.
+pub(crate) struct LzDecoder<R: Read> {
+    reader: BufReader<R>,
+    member: Option<Member>,
+    done: bool,
+}
+
+/// state of the member being decoded
+struct Member {
+    stream: Stream,
+    lzma_header: [u8; LZMA_HEADER_LEN],
+    lzma_header_pos: usize,
+    crc: Hasher,
+    data_size: u64,
+    member_size: u64,
+}
+
+impl<R: Read> LzDecoder<R> {
+    pub(crate) fn new(reader: R) -> io::Result<Self> {
+        let mut reader = BufReader::new(reader);
+        let member = Member::start(&mut reader)?.ok_or_else(|| invalid("invalid lzip header"))?;
+        Ok(LzDecoder {
+            reader,
+            member: Some(member),
+            done: false,
+        })
+    }
+}
+
+impl<R: Read> Read for LzDecoder<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while !self.done && !buf.is_empty() {
+            let member = match &mut self.member {
+                Some(member) => member,
+                None => match Member::start(&mut self.reader)? {
+                    Some(member) => self.member.insert(member),
+                    None => {
+                        self.done = true;
+                        break;
+                    }
+                },
+            };
+            let (len, ended) = member.decode(&mut self.reader, buf)?;
+            if ended {
+                member.check_trailer(&mut self.reader)?;
+                self.member = None;
+            }
+            if len > 0 {
+                return Ok(len);
+            }
+        }
+        Ok(0)
+    }
+}
+
+impl Member {
+    /// reads the header of the next member, if any
+    fn start(reader: &mut impl BufRead) -> io::Result<Option<Self>> {
+        let mut header = [0; LZIP_HEADER_LEN];
+        let len = read_up_to(reader, &mut header)?;
+        if len < LZIP_HEADER_LEN || !header.starts_with(b"LZIP") {
+            return Ok(None);
+        }
+        if header[4] != 1 {
+            return Err(invalid("unsupported lzip version"));
+        }
+        let dictionary_size =
+            dictionary_size(header[5]).ok_or_else(|| invalid("invalid lzip dictionary size"))?;
+
+        // the uncompressed size is left unknown (all 0xff), lzip members are
+        // always terminated by an end of stream marker
+        let mut lzma_header = [0xff; LZMA_HEADER_LEN];
+        lzma_header[0] = LZMA_PROPERTIES;
+        lzma_header[1..5].copy_from_slice(&dictionary_size.to_le_bytes());
+
+        Ok(Some(Member {
+            stream: Stream::new_lzma_decoder(u64::MAX)?,
+            lzma_header,
+            lzma_header_pos: 0,
+            crc: Hasher::new(),
+            data_size: 0,
+            member_size: LZIP_HEADER_LEN as u64,
+        }))
+    }
+
+    /// decodes some data of the member into `buf`, returning the number of
+    /// bytes decoded and whether the end of the member was reached
+    fn decode(&mut self, reader: &mut impl BufRead, buf: &mut [u8]) -> io::Result<(usize, bool)> {
+        let from_header = self.lzma_header_pos < LZMA_HEADER_LEN;
+        let input = if from_header {
+            &self.lzma_header[self.lzma_header_pos..]
+        } else {
+            reader.fill_buf()?
+        };
+        if input.is_empty() {
+            return Err(io::Error::new(
+                ErrorKind::UnexpectedEof,
+                "truncated lzip member",
+            ));
+        }
+
+        let (total_in, total_out) = (self.stream.total_in(), self.stream.total_out());
+        let status = self.stream.process(input, buf, Action::Run)?;
+        let consumed = (self.stream.total_in() - total_in) as usize;
+        let len = (self.stream.total_out() - total_out) as usize;
+        if consumed == 0 && len == 0 && status != Status::StreamEnd {
+            return Err(invalid("corrupted lzip data"));
+        }
+
+        if from_header {
+            self.lzma_header_pos += consumed;
+        } else {
+            reader.consume(consumed);
+            self.member_size += consumed as u64;
+        }
+        self.crc.update(&buf[..len]);
+        self.data_size += len as u64;
+        Ok((len, status == Status::StreamEnd))
+    }
+
+    /// checks the trailer of the member against the decoded data
+    fn check_trailer(&mut self, reader: &mut impl BufRead) -> io::Result<()> {
+        let mut trailer = [0; LZIP_TRAILER_LEN];
+        if read_up_to(reader, &mut trailer)? < LZIP_TRAILER_LEN {
+            return Err(io::Error::new(
+                ErrorKind::UnexpectedEof,
+                "truncated lzip trailer",
+            ));
+        }
+        let crc = u32::from_le_bytes(trailer[0..4].try_into().unwrap());
+        let data_size = u64::from_le_bytes(trailer[4..12].try_into().unwrap());
+        let member_size = u64::from_le_bytes(trailer[12..20].try_into().unwrap());
+
+        if crc != self.crc.clone().finalize() {
+            return Err(invalid("lzip CRC mismatch"));
+        }
+        if data_size != self.data_size {
+            return Err(invalid("lzip data size mismatch"));
+        }
+        if member_size != self.member_size + LZIP_TRAILER_LEN as u64 {
+            return Err(invalid("lzip member size mismatch"));
+        }
+        Ok(())
+    }
+}
+
+/// reads as many bytes as possible into `buf`, stopping early at the end of
+/// the input only
+fn read_up_to(reader: &mut impl Read, buf: &mut [u8]) -> io::Result<usize> {
+    let mut len = 0;
+    while len < buf.len() {
+        match reader.read(&mut buf[len..]) {
+            Ok(0) => break,
+            Ok(n) => len += n,
+            Err(err) if err.kind() == ErrorKind::Interrupted => {}
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(len)
+}
+
+/// decodes the dictionary size stored in the header of an lzip member:
+/// bits 4-0 hold the base 2 logarithm of a base size, from which bits 7-5
+/// subtract that many sixteenths.
+fn dictionary_size(coded: u8) -> Option<u32> {
+    let exponent = coded & 0x1f;
+    if !(12..=29).contains(&exponent) {
+        return None;
+    }
+    let base = 1u32 << exponent;
+    Some(base - (base / 16) * u32::from(coded >> 5))
+}
+
+/// I/O error reported as an [`Error::InvalidArchive`] once converted
+fn invalid(message: &'static str) -> io::Error {
+    io::Error::new(ErrorKind::InvalidData, Error::invalid_archive(message))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use xz2::stream::LzmaOptions;
+
+    /// compresses `data` into a single lzip member
+    fn member(data: &[u8]) -> Vec<u8> {
+        let options = LzmaOptions::new_preset(6).unwrap();
+        let mut stream = Stream::new_lzma_encoder(&options).unwrap();
+        let mut lzma = Vec::with_capacity(data.len() + 1024);
+        stream.process_vec(data, &mut lzma, Action::Finish).unwrap();
+
+        // the default dictionary of the preset (8 MiB) is coded as 0x17
+        let mut member = b"LZIP\x01\x17".to_vec();
+        member.extend_from_slice(&lzma[LZMA_HEADER_LEN..]);
+        let member_size = (member.len() + LZIP_TRAILER_LEN) as u64;
+        member.extend_from_slice(&crc32fast::hash(data).to_le_bytes());
+        member.extend_from_slice(&(data.len() as u64).to_le_bytes());
+        member.extend_from_slice(&member_size.to_le_bytes());
+        member
+    }
+
+    fn decode(data: &[u8]) -> Result<Vec<u8>, Error> {
+        let mut decoded = vec![];
+        LzDecoder::new(data)?.read_to_end(&mut decoded)?;
+        Ok(decoded)
+    }
+
+    #[test]
+    fn decode_dictionary_size() {
+        assert_eq!(dictionary_size(0x0c), Some(4 << 10));
+        assert_eq!(dictionary_size(0x17), Some(8 << 20));
+        assert_eq!(dictionary_size(0xd4), Some((1 << 20) - 6 * (1 << 16)));
+        assert_eq!(dictionary_size(0x1d), Some(512 << 20));
+        assert_eq!(dictionary_size(0x0b), None);
+        assert_eq!(dictionary_size(0x1e), None);
+    }
+
+    #[test]
+    fn invalid_header() {
+        assert!(matches!(
+            decode(b"LZMA\x01\x17"),
+            Err(Error::InvalidArchive { .. })
+        ));
+        assert!(matches!(
+            decode(b"LZIP\x01\x0b"),
+            Err(Error::InvalidArchive { .. })
+        ));
+    }
+
+    #[test]
+    fn decode_members() {
+        let mut data = member(b"hello ");
+        data.extend(member(b"world\n"));
+        assert_eq!(decode(&data).unwrap(), b"hello world\n");
+
+        // data following the last member is ignored
+        data.extend_from_slice(&[0; 16]);
+        assert_eq!(decode(&data).unwrap(), b"hello world\n");
+    }
+
+    #[test]
+    fn corrupted_trailer() {
+        let data = member(b"hello world\n");
+        let trailer = data.len() - LZIP_TRAILER_LEN;
+        for offset in [0, 4, 12] {
+            let mut corrupted = data.clone();
+            corrupted[trailer + offset] ^= 1;
+            assert!(matches!(
+                decode(&corrupted),
+                Err(Error::InvalidArchive { .. })
+            ));
+        }
+        assert!(matches!(decode(&data[..data.len() - 1]), Err(Error::Io(_))));
+    }
+}
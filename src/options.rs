@@ -0,0 +1,96 @@
+use std::path::Path;
+
+use crate::{Entry, Result};
+
+/// Options controlling how an [`Archive`](crate::Archive) is read and unpacked.
+///
+/// # Example
+///
+/// ```no_run
+/// use arkiv::{Archive, ArchiveOptions, Result};
+///
+/// fn main() -> Result<()> {
+///     let options = ArchiveOptions {
+///         executable_extensions: vec!["sh".to_string(), "py".to_string()],
+///         ..Default::default()
+///     };
+///     let mut archive = Archive::open("path/to/archive.zip")?.with_options(options);
+///     archive.unpack("/tmp/extracted/")?;
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ArchiveOptions {
+    /// Skip known junk prefixes (e.g. a UTF-8 byte order mark) at the start
+    /// of the archive. See [`Archive::lenient`](crate::Archive::lenient).
+    pub lenient: bool,
+
+    /// Extensions (e.g. `sh`, `py`) of the files that should be made executable
+    /// when they are unpacked, regardless of the permissions stored in the
+    /// archive. This only has an effect on unix systems.
+    pub executable_extensions: Vec<String>,
+
+    /// Fail with [`Error::EntryTypeNotSupported`](crate::Error::EntryTypeNotSupported)
+    /// when unpacking an archive containing entries that are neither files,
+    /// directories nor links (e.g. devices or FIFOs), instead of silently
+    /// skipping them. See [`Entry::is_other`].
+    pub strict_entry_types: bool,
+
+    /// Check that the magic bytes at the start of the archive match its
+    /// format when it is first read, failing with
+    /// [`Error::InvalidArchive`](crate::Error::InvalidArchive) otherwise,
+    /// instead of attempting to decode it (e.g. a tar archive opened as a zip
+    /// archive with [`Archive::open_with_format`](crate::Archive::open_with_format)).
+    /// Streamed archives are not checked.
+    pub validate_format: bool,
+
+    /// Dictionary used to decompress [`Format::TarZstd`](crate::Format::TarZstd)
+    /// and [`Format::Zstd`](crate::Format::Zstd) archives that were compressed
+    /// with a dictionary, e.g. using
+    /// [`ArchiveBuilder::with_zstd_dictionary`](crate::ArchiveBuilder::with_zstd_dictionary).
+    #[cfg(feature = "zstd")]
+    pub zstd_dictionary: Option<Vec<u8>>,
+}
+
+/// What to do with the entries that are not located under the prefix passed
+/// to [`Archive::unpack_with_strip_prefix`](crate::Archive::unpack_with_strip_prefix).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Unmatched {
+    /// Unpack the entry at its original path.
+    Keep,
+    /// Do not unpack the entry.
+    Skip,
+}
+
+impl ArchiveOptions {
+    fn is_executable_extension(&self, path: &Path) -> bool {
+        match path.extension() {
+            Some(ext) => self
+                .executable_extensions
+                .iter()
+                .any(|candidate| ext.eq_ignore_ascii_case(candidate.trim_start_matches('.'))),
+            None => false,
+        }
+    }
+
+    /// adds the executable bits to an unpacked entry if its extension
+    /// is listed in `executable_extensions`.
+    #[cfg(unix)]
+    pub(crate) fn apply_executable_extensions(&self, entry: &Entry, dest: &Path) -> Result<()> {
+        use crate::archive::safe_join;
+        use std::{fs, os::unix::fs::PermissionsExt};
+
+        if entry.is_file() && self.is_executable_extension(entry.path()) {
+            let outpath = safe_join(dest, entry.path())?;
+            let mut permissions = fs::metadata(&outpath)?.permissions();
+            permissions.set_mode(permissions.mode() | 0o111);
+            fs::set_permissions(&outpath, permissions)?;
+        }
+        Ok(())
+    }
+
+    #[cfg(not(unix))]
+    pub(crate) fn apply_executable_extensions(&self, _entry: &Entry, _dest: &Path) -> Result<()> {
+        Ok(())
+    }
+}
@@ -1,4 +1,10 @@
-use std::{fmt::Display, io};
+use std::{
+    fmt::Display,
+    io,
+    path::{Path, PathBuf},
+};
+
+use crate::EntryType;
 
 /// Error type used throughout this crate
 #[derive(Debug)]
@@ -7,27 +13,126 @@ pub enum Error {
     Io(std::io::Error),
 
     /// This file is not a valid archive
-    InvalidArchive(&'static str),
+    InvalidArchive {
+        /// path of the archive, if known (e.g. not for an archive in memory)
+        path: Option<PathBuf>,
+        /// what is wrong with the archive
+        message: String,
+    },
 
     /// This archive is not supported
-    UnsupportedArchive(&'static str),
+    UnsupportedArchive {
+        /// path of the archive, if known (e.g. not for an archive in memory)
+        path: Option<PathBuf>,
+        /// why the archive is not supported
+        message: String,
+    },
+
+    /// The format of this file could not be recognized from its extension
+    UnrecognizedFormat(PathBuf),
+
+    /// The archive file does not exist
+    ArchiveNotFound(PathBuf),
 
     /// The requested file could not be found in the archive
     FileNotFound,
 
+    /// The archive is encrypted and cannot be read without a password
+    /// (e.g. a password-protected zip archive)
+    PasswordRequired,
+
+    /// The requested entry exists but its type is not supported
+    /// for this operation (e.g. unpacking a device file)
+    EntryTypeNotSupported(EntryType),
+
+    /// The contents of the entry at this path are not valid UTF-8
+    InvalidEncoding(PathBuf),
+
+    /// The entry at this path would be unpacked outside of the destination
+    /// directory (e.g. `../../etc/passwd`)
+    UnsafePath(PathBuf),
+
+    /// The contents of the archive does not match the expected list of entries
+    ContentMismatch {
+        /// expected entries that are not present in the archive
+        missing: Vec<PathBuf>,
+        /// entries present in the archive that were not expected
+        unexpected: Vec<PathBuf>,
+    },
+
+    /// An error caused by I/O when unpacking the entry at this path
+    Entry {
+        /// path of the entry within the archive
+        path: PathBuf,
+        /// underlying I/O error
+        source: io::Error,
+    },
+
+    /// The provided glob pattern is invalid
+    InvalidPattern(String),
+
     /// The URL to the archive is invalid
     InvalidUrl(String),
 
     /// An error occurred when downloading an archive
     InvalidRequest(String),
+
+    /// The environment variable with this name is not set
+    MissingEnvVar(String),
 }
 
 /// Result type used throughout this crate
 pub type Result<T> = std::result::Result<T, Error>;
 
+impl Error {
+    /// invalid archive error, the path of which is added by [`Error::with_path`]
+    pub(crate) fn invalid_archive(message: impl Into<String>) -> Self {
+        Error::InvalidArchive {
+            path: None,
+            message: message.into(),
+        }
+    }
+
+    /// unsupported archive error, the path of which is added by [`Error::with_path`]
+    pub(crate) fn unsupported_archive(message: impl Into<String>) -> Self {
+        Error::UnsupportedArchive {
+            path: None,
+            message: message.into(),
+        }
+    }
+
+    /// adds the path of the archive to an invalid or unsupported archive
+    /// error, unless it is empty (e.g. an archive in memory) or already known
+    pub(crate) fn with_path(mut self, archive_path: &Path) -> Self {
+        if let Error::InvalidArchive { path, .. } | Error::UnsupportedArchive { path, .. } =
+            &mut self
+        {
+            if path.is_none() && !archive_path.as_os_str().is_empty() {
+                *path = Some(archive_path.to_path_buf());
+            }
+        }
+        self
+    }
+
+    /// adds the path of the entry being unpacked to an I/O error
+    pub(crate) fn in_entry(self, entry_path: &Path) -> Self {
+        match self {
+            Error::Io(source) => Error::Entry {
+                path: entry_path.to_path_buf(),
+                source,
+            },
+            err => err,
+        }
+    }
+}
+
 impl From<io::Error> for Error {
     fn from(err: io::Error) -> Self {
-        Error::Io(err)
+        // the decoders report invalid archives through the I/O errors of `Read`
+        match err.get_ref().map(|inner| inner.is::<Error>()) {
+            Some(true) => *err.into_inner().unwrap().downcast::<Error>().unwrap(),
+            _ => Error::Io(err),
+        }
     }
 }
 
@@ -35,13 +140,66 @@ impl Display for Error {
     fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Error::Io(err) => write!(fmt, "{err}"),
-            Error::InvalidArchive(err) => write!(fmt, "invalid archive: {err}"),
-            Error::UnsupportedArchive(err) => write!(fmt, "unsupported archive: {err}"),
+            Error::InvalidArchive {
+                path: Some(path),
+                message,
+            } => write!(fmt, "invalid archive '{}': {message}", path.display()),
+            Error::InvalidArchive {
+                path: None,
+                message,
+            } => {
+                write!(fmt, "invalid archive: {message}")
+            }
+            Error::UnsupportedArchive {
+                path: Some(path),
+                message,
+            } => write!(fmt, "unsupported archive '{}': {message}", path.display()),
+            Error::UnsupportedArchive {
+                path: None,
+                message,
+            } => {
+                write!(fmt, "unsupported archive: {message}")
+            }
+            Error::UnrecognizedFormat(path) => {
+                write!(fmt, "unrecognized archive format: '{}'", path.display())
+            }
+            Error::ArchiveNotFound(path) => write!(fmt, "archive not found: '{}'", path.display()),
             Error::FileNotFound => write!(fmt, "specified file not found in archive"),
+            Error::PasswordRequired => write!(fmt, "password required to read encrypted archive"),
+            Error::EntryTypeNotSupported(entry_type) => {
+                write!(fmt, "entry type not supported: {entry_type:?}")
+            }
+            Error::InvalidEncoding(path) => {
+                write!(fmt, "invalid UTF-8 contents: '{}'", path.display())
+            }
+            Error::UnsafePath(path) => {
+                write!(fmt, "unsafe entry path: '{}'", path.display())
+            }
+            Error::ContentMismatch {
+                missing,
+                unexpected,
+            } => write!(
+                fmt,
+                "archive contents mismatch: missing {missing:?}, unexpected {unexpected:?}"
+            ),
+            Error::Entry { path, source } => {
+                write!(fmt, "failed to extract '{}': {source}", path.display())
+            }
+            Error::InvalidPattern(err) => write!(fmt, "invalid pattern: {err}"),
             Error::InvalidUrl(url) => write!(fmt, "invalid url: '{url}'"),
             Error::InvalidRequest(err) => write!(fmt, "{err}"),
+            Error::MissingEnvVar(name) => {
+                write!(fmt, "environment variable not set: '{name}'")
+            }
         }
     }
 }
 
-impl std::error::Error for Error {}
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Entry { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
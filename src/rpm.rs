@@ -0,0 +1,92 @@
+use std::io::{self, Cursor, Read};
+
+#[cfg(feature = "gzip")]
+use flate2::read::GzDecoder;
+
+#[cfg(feature = "bzip2")]
+use bzip2::read::BzDecoder;
+
+#[cfg(feature = "xz")]
+use xz2::read::XzDecoder;
+
+#[cfg(feature = "zstd")]
+use zstd::stream::Decoder as ZstdDecoder;
+
+use crate::{Error, Format, Result};
+
+/// magic bytes at the start of the lead of an rpm package
+const LEAD_MAGIC: &[u8] = b"\xed\xab\xee\xdb";
+
+/// size of the lead, which is followed by the signature header
+const LEAD_SIZE: usize = 96;
+
+/// magic bytes at the start of the signature and main headers
+const HEADER_MAGIC: &[u8] = b"\x8e\xad\xe8\x01";
+
+/// size of the intro of a header, followed by its index and its data store
+const HEADER_INTRO_SIZE: u64 = 16;
+
+/// size of an entry of the index of a header
+const INDEX_ENTRY_SIZE: u64 = 16;
+
+/// skips a header (signature or main header) and returns its size
+fn skip_header(reader: &mut impl Read) -> Result<u64> {
+    let invalid_header = || Error::invalid_archive("invalid rpm header");
+    let mut intro = [0; HEADER_INTRO_SIZE as usize];
+    reader
+        .read_exact(&mut intro)
+        .map_err(|_| invalid_header())?;
+    if !intro.starts_with(HEADER_MAGIC) {
+        Err(invalid_header())?;
+    }
+    let index_len = u32::from_be_bytes([intro[8], intro[9], intro[10], intro[11]]);
+    let store_size = u32::from_be_bytes([intro[12], intro[13], intro[14], intro[15]]);
+    let len = u64::from(index_len) * INDEX_ENTRY_SIZE + u64::from(store_size);
+    if io::copy(&mut reader.take(len), &mut io::sink())? != len {
+        Err(Error::invalid_archive("truncated rpm header"))?;
+    }
+    Ok(HEADER_INTRO_SIZE + len)
+}
+
+/// Returns a reader over the decompressed payload of an rpm package, i.e. a
+/// cpio archive located after the lead, the signature header and the main
+/// header. The compression of the payload is detected from its magic bytes.
+pub(crate) fn payload(mut reader: impl Read + 'static) -> Result<Box<dyn Read>> {
+    let mut lead = [0; LEAD_SIZE];
+    reader
+        .read_exact(&mut lead)
+        .map_err(|_| Error::invalid_archive("invalid rpm lead"))?;
+    if !lead.starts_with(LEAD_MAGIC) {
+        Err(Error::invalid_archive("invalid rpm lead"))?;
+    }
+
+    // the signature header is padded to a multiple of 8 bytes, unlike the main header
+    let signature_size = skip_header(&mut reader)?;
+    let padding = (8 - signature_size % 8) % 8;
+    io::copy(&mut (&mut reader).take(padding), &mut io::sink())?;
+    skip_header(&mut reader)?;
+
+    let mut magic = vec![];
+    (&mut reader).take(6).read_to_end(&mut magic)?;
+    let reader = Cursor::new(magic.clone()).chain(reader);
+    match Format::infer_from_magic_bytes(&magic) {
+        // uncompressed payload
+        _ if magic.starts_with(b"0707") => Ok(Box::new(reader)),
+
+        #[cfg(feature = "gzip")]
+        Format::Gzip => Ok(Box::new(GzDecoder::new(reader))),
+
+        #[cfg(feature = "bzip2")]
+        Format::Bzip2 => Ok(Box::new(BzDecoder::new(reader))),
+
+        #[cfg(feature = "xz")]
+        Format::Xz2 => Ok(Box::new(XzDecoder::new(reader))),
+
+        #[cfg(feature = "zstd")]
+        Format::Zstd => Ok(Box::new(ZstdDecoder::new(reader)?)),
+
+        _ => Err(Error::unsupported_archive(
+            "unsupported rpm payload compression, did you enable the proper feature?",
+        )),
+    }
+}
@@ -0,0 +1,71 @@
+use crate::Entry;
+
+/// Summary of the entries of an archive, gathered in a single pass over
+/// the archive.
+///
+/// See [`Archive::scan`](crate::Archive::scan).
+#[derive(Debug, Clone, Default)]
+pub struct ArchiveScan {
+    /// number of regular files
+    pub file_count: usize,
+
+    /// number of directories
+    pub dir_count: usize,
+
+    /// number of other entries (e.g. symbolic links)
+    pub other_count: usize,
+
+    /// total uncompressed size of all the entries
+    pub total_size: u64,
+
+    /// smallest regular file, the first one in case of a tie
+    pub smallest: Option<Entry>,
+
+    /// largest regular file, the first one in case of a tie
+    pub largest: Option<Entry>,
+
+    /// entry with the fewest path components, the first one in case of a tie
+    pub shallowest: Option<Entry>,
+
+    /// entry with the most path components, the first one in case of a tie
+    pub deepest: Option<Entry>,
+}
+
+fn depth(entry: &Entry) -> usize {
+    entry.path().components().count()
+}
+
+/// Returns `true` if there is no current entry yet, or if `better` prefers
+/// the new entry to the current one.
+fn replaces(current: &Option<Entry>, better: impl FnOnce(&Entry) -> bool) -> bool {
+    match current {
+        Some(current) => better(current),
+        None => true,
+    }
+}
+
+impl ArchiveScan {
+    pub(crate) fn add(&mut self, entry: Entry) {
+        if entry.is_file() {
+            self.file_count += 1;
+            if replaces(&self.smallest, |e| entry.size() < e.size()) {
+                self.smallest = Some(entry.clone());
+            }
+            if replaces(&self.largest, |e| entry.size() > e.size()) {
+                self.largest = Some(entry.clone());
+            }
+        } else if entry.is_dir() {
+            self.dir_count += 1;
+        } else {
+            self.other_count += 1;
+        }
+        self.total_size += entry.size();
+
+        if replaces(&self.shallowest, |e| depth(&entry) < depth(e)) {
+            self.shallowest = Some(entry.clone());
+        }
+        if replaces(&self.deepest, |e| depth(&entry) > depth(e)) {
+            self.deepest = Some(entry);
+        }
+    }
+}
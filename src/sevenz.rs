@@ -0,0 +1,230 @@
+use std::{
+    fs::create_dir_all,
+    io::{self, Cursor, Read, Seek, SeekFrom},
+    ops::ControlFlow,
+    path::{Component, Path, PathBuf},
+};
+
+use sevenz_rust::{Error as SevenZError, Password, SevenZArchiveEntry, SevenZReader};
+
+use crate::{
+    archive::{extract, Archived, OnUnpacked, Visitor},
+    entry::EntryType,
+    Entries, Entry, Error, Result,
+};
+
+/// message of the error returned when the CRC-32 of an entry does not match
+const CHECKSUM_VERIFICATION_FAILED: &str = "7z checksum verification failed";
+
+impl From<SevenZError> for Error {
+    fn from(value: SevenZError) -> Self {
+        match value {
+            SevenZError::Io(err, _) => Error::Io(err),
+            SevenZError::FileOpen(err, _) => Error::Io(err),
+            SevenZError::UnsupportedCompressionMethod(_) => {
+                Error::unsupported_archive("unsupported 7z compression method")
+            }
+            SevenZError::PasswordRequired => Error::PasswordRequired,
+            SevenZError::ChecksumVerificationFailed => {
+                Error::invalid_archive(CHECKSUM_VERIFICATION_FAILED)
+            }
+            _ => Error::invalid_archive("invalid 7z archive"),
+        }
+    }
+}
+
+/// extracts the 7z error reported through the I/O error of the reader of an
+/// entry, e.g. when its checksum does not match once it has been read
+fn into_sevenz_error(err: Error) -> std::result::Result<SevenZError, Error> {
+    match err {
+        Error::Io(err) if err.get_ref().is_some_and(|inner| inner.is::<SevenZError>()) => {
+            Ok(*err.into_inner().unwrap().downcast::<SevenZError>().unwrap())
+        }
+        err => Err(err),
+    }
+}
+
+/// attribute flag indicating that the unix mode is stored in the upper 16 bits
+const FILE_ATTRIBUTE_UNIX_EXTENSION: u32 = 0x8000;
+
+fn to_entry(index: usize, file: &SevenZArchiveEntry) -> Result<Entry> {
+    let mut path = PathBuf::from(file.name());
+    if !path
+        .components()
+        .all(|component| matches!(component, Component::Normal(_)))
+    {
+        Err(Error::invalid_archive("invalid filename"))?;
+    }
+    let entry_type = if file.is_directory() {
+        // 7z does not store the trailing slash of directories, unlike zip and tar
+        path.as_mut_os_string().push("/");
+        EntryType::Directory
+    } else {
+        EntryType::File
+    };
+    let unix_mode = (file.has_windows_attributes
+        && file.windows_attributes & FILE_ATTRIBUTE_UNIX_EXTENSION != 0)
+        .then_some(file.windows_attributes >> 16);
+    let entry = Entry {
+        index,
+        path,
+        size: file.size(),
+        compressed_size: None,
+        entry_type,
+        creator_os: None,
+        unix_mode,
+        modified: None,
+        uid: None,
+        gid: None,
+        uname: None,
+        gname: None,
+    };
+    Ok(entry)
+}
+
+/// 7z backend.
+///
+/// The entries of a 7z archive are usually compressed together in solid
+/// blocks, so their contents can only be read by decompressing the archive
+/// from the start of the block.
+pub(crate) struct SevenZ<R: Read + Seek> {
+    inner: SevenZReader<R>,
+}
+
+impl<R: Read + Seek> SevenZ<R> {
+    pub(crate) fn new(mut reader: R) -> Result<Self> {
+        let len = reader.seek(SeekFrom::End(0))?;
+        reader.rewind()?;
+        let inner = SevenZReader::new(reader, len, Password::empty())?;
+        Ok(SevenZ { inner })
+    }
+
+    /// visits the entries along with their contents, in the order in which
+    /// they are stored in the archive (which differs from the order of the
+    /// entries in the header).
+    fn visit_entries(&mut self, visit: &mut Visitor) -> Result<()> {
+        self.try_visit_entries(visit)?
+    }
+
+    /// same as [`SevenZ::visit_entries`], but returns the errors of the 7z
+    /// decoder separately from the ones of the visitor
+    fn try_visit_entries(
+        &mut self,
+        visit: &mut Visitor,
+    ) -> std::result::Result<Result<()>, SevenZError> {
+        let files = &self.inner.archive().files;
+        let names: Vec<String> = files.iter().map(|file| file.name().to_string()).collect();
+        let mut visited = vec![false; names.len()];
+        let mut result = Ok(());
+
+        self.inner.for_each_entries(|file, reader| {
+            let index = (0..names.len()).find(|&i| !visited[i] && names[i] == file.name());
+            let Some(index) = index else {
+                return Ok(true);
+            };
+            visited[index] = true;
+            let flow = to_entry(index, file)
+                .and_then(|entry| visit(entry, reader))
+                // the remaining contents must be consumed to reach the next entry
+                .and_then(|flow| Ok(io::copy(reader, &mut io::sink()).map(|_| flow)?));
+            match flow.map_err(into_sevenz_error) {
+                Ok(flow) => Ok(flow.is_continue()),
+                Err(Ok(err)) => Err(err),
+                Err(Err(err)) => {
+                    result = Err(err);
+                    Ok(false)
+                }
+            }
+        })?;
+        Ok(result)
+    }
+}
+
+impl<R: Read + Seek> Archived for SevenZ<R> {
+    fn unpack(&mut self, dest: &Path, on_unpacked: &mut OnUnpacked) -> Result<()> {
+        create_dir_all(dest)?;
+        self.visit_entries(&mut |entry, reader| {
+            extract(&entry, reader, dest)?;
+            on_unpacked();
+            Ok(ControlFlow::Continue(()))
+        })
+    }
+
+    fn entries(&mut self) -> Result<Entries> {
+        let entries: Vec<_> = self
+            .inner
+            .archive()
+            .files
+            .iter()
+            .enumerate()
+            .map(|(index, file)| to_entry(index, file))
+            .collect();
+        Ok(Box::new(entries.into_iter()))
+    }
+
+    fn unpack_entry(&mut self, entry: &Entry, dest: &Path) -> Result<()> {
+        if entry.is_dir() {
+            return extract(entry, &mut io::empty(), dest);
+        }
+        let mut found = false;
+        self.visit_entries(&mut |current, reader| {
+            if current.index() != entry.index() {
+                return Ok(ControlFlow::Continue(()));
+            }
+            found = true;
+            extract(&current, reader, dest)?;
+            Ok(ControlFlow::Break(()))
+        })?;
+        if !found {
+            Err(Error::FileNotFound)?;
+        }
+        Ok(())
+    }
+
+    fn walk(&mut self, visit: &mut Visitor) -> Result<()> {
+        self.visit_entries(visit)
+    }
+
+    fn entry_reader(&mut self, entry: &Entry) -> Result<Box<dyn Read + '_>> {
+        // the contents cannot be streamed out of the solid block, they are
+        // decompressed in memory instead
+        let mut data = None;
+        self.visit_entries(&mut |current, reader| {
+            if current.index() != entry.index() {
+                return Ok(ControlFlow::Continue(()));
+            }
+            let mut buffer = vec![];
+            reader.read_to_end(&mut buffer)?;
+            data = Some(buffer);
+            Ok(ControlFlow::Break(()))
+        })?;
+        let data = data.ok_or(Error::FileNotFound)?;
+        Ok(Box::new(Cursor::new(data)))
+    }
+
+    fn verify_entry(&mut self, entry: &Entry) -> Result<bool> {
+        // the CRC-32 of the entry is checked once it has been read entirely
+        let mut found = entry.is_dir();
+        let result = self.try_visit_entries(&mut |current, reader| {
+            if current.index() != entry.index() {
+                return Ok(ControlFlow::Continue(()));
+            }
+            found = true;
+            io::copy(reader, &mut io::sink())?;
+            Ok(ControlFlow::Break(()))
+        });
+        match result {
+            Ok(result) => result?,
+            Err(SevenZError::ChecksumVerificationFailed) => return Ok(false),
+            Err(err) => Err(err)?,
+        }
+        if !found {
+            Err(Error::FileNotFound)?;
+        }
+        Ok(true)
+    }
+
+    fn entry_count_hint(&self) -> Option<usize> {
+        Some(self.inner.archive().files.len())
+    }
+}
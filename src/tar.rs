@@ -1,11 +1,181 @@
 use std::{
+    borrow::Cow,
+    cell::RefCell,
     fs::create_dir_all,
     io::{self, Read},
     iter::Enumerate,
     path::Path,
+    rc::Rc,
+    time::{Duration, UNIX_EPOCH},
 };
 
-use crate::{archive::Archived, entry::EntryType, Entries, Entry, Error, Result};
+use crate::{
+    archive::{safe_join, unpack_hardlink, unpack_symlink, Archived, OnUnpacked, Visitor},
+    entry::EntryType,
+    Entries, Entry, Error, Result,
+};
+
+/// size of the blocks of a tar archive, including the headers
+const BLOCK_SIZE: usize = 512;
+
+/// backend of the tar archives
+pub(crate) struct Tar<R: Read> {
+    inner: tar::Archive<LastBlockReader<R>>,
+    last_block: Rc<RefCell<LastBlock>>,
+}
+
+impl<R: Read> Tar<R> {
+    pub(crate) fn new(reader: R) -> Self {
+        let last_block = Rc::new(RefCell::new(LastBlock {
+            data: Vec::with_capacity(BLOCK_SIZE),
+            end: 0,
+        }));
+        let reader = LastBlockReader {
+            inner: reader,
+            last_block: last_block.clone(),
+        };
+        let inner = tar::Archive::new(reader);
+        Tar { inner, last_block }
+    }
+}
+
+/// Reader keeping a copy of the last block read from the underlying reader,
+/// i.e. the header of an entry that the `tar` crate refused to parse.
+struct LastBlockReader<R> {
+    inner: R,
+    last_block: Rc<RefCell<LastBlock>>,
+}
+
+/// last block read from a tar archive
+struct LastBlock {
+    data: Vec<u8>,
+    /// offset, in the archive, of the end of the block
+    end: u64,
+}
+
+impl LastBlock {
+    /// returns the block if it is complete and starts at `offset`
+    fn at(&self, offset: u64) -> Option<&[u8]> {
+        let start = self.end.checked_sub(BLOCK_SIZE as u64)?;
+        (self.data.len() == BLOCK_SIZE && start == offset).then_some(&self.data)
+    }
+}
+
+impl<R: Read> Read for LastBlockReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let count = self.inner.read(buf)?;
+        let read = &buf[..count];
+        let mut last_block = self.last_block.borrow_mut();
+        let kept = BLOCK_SIZE
+            .saturating_sub(read.len())
+            .min(last_block.data.len());
+        let dropped = last_block.data.len() - kept;
+        last_block.data.drain(..dropped);
+        last_block
+            .data
+            .extend_from_slice(&read[read.len().saturating_sub(BLOCK_SIZE)..]);
+        last_block.end += count as u64;
+        Ok(count)
+    }
+}
+
+/// checksum of a tar header, computed with the checksum field itself filled with spaces
+fn header_checksum(header: &tar::Header) -> u32 {
+    let bytes = header.as_bytes();
+    bytes[..148]
+        .iter()
+        .chain(&[b' '; 8])
+        .chain(&bytes[156..])
+        .map(|byte| u32::from(*byte))
+        .sum()
+}
+
+/// size of the data of an entry, rounded up to a whole number of blocks
+fn padded_size(size: u64) -> u64 {
+    size.div_ceil(BLOCK_SIZE as u64) * BLOCK_SIZE as u64
+}
+
+/// whether the `tar` crate refused to read an entry because of the checksum of its header
+fn is_checksum_mismatch(err: &io::Error) -> bool {
+    err.kind() == io::ErrorKind::Other && err.to_string() == "archive header checksum mismatch"
+}
+
+fn to_entry<R: Read>(index: usize, orig_tar_entry: &tar::Entry<R>) -> Result<Entry> {
+    let path = orig_tar_entry.path()?.to_path_buf();
+    let size = orig_tar_entry.size();
+    let header = orig_tar_entry.header();
+    let entry_type = match header.entry_type() {
+        tar::EntryType::Regular | tar::EntryType::Continuous | tar::EntryType::GNUSparse => {
+            EntryType::File
+        }
+        tar::EntryType::Directory => EntryType::Directory,
+        tar::EntryType::Symlink => match orig_tar_entry.link_name()? {
+            Some(target) => EntryType::Symlink {
+                target: target.into_owned(),
+            },
+            None => EntryType::Other,
+        },
+        tar::EntryType::Link => match orig_tar_entry.link_name()? {
+            Some(target) => EntryType::Hardlink {
+                target: target.into_owned(),
+            },
+            None => EntryType::Other,
+        },
+        _ => EntryType::Other,
+    };
+    let entry = Entry {
+        index,
+        path,
+        size,
+        compressed_size: None,
+        entry_type,
+        creator_os: None,
+        unix_mode: header.mode().ok(),
+        modified: header
+            .mtime()
+            .ok()
+            .map(|mtime| UNIX_EPOCH + Duration::from_secs(mtime)),
+        uid: header.uid().ok(),
+        gid: header.gid().ok(),
+        uname: owner_name(header.username()),
+        gname: owner_name(header.groupname()),
+    };
+    Ok(entry)
+}
+
+/// user or group name of a tar header, `None` if missing, empty or not valid UTF-8
+fn owner_name(name: std::result::Result<Option<&str>, std::str::Utf8Error>) -> Option<String> {
+    name.ok()
+        .flatten()
+        .filter(|name| !name.is_empty())
+        .map(String::from)
+}
+
+/// warns about an absolute entry path, whose leading `/` is stripped when unpacked
+#[allow(unused)]
+fn warn_if_absolute(path: &Path) {
+    #[cfg(feature = "tracing")]
+    if path.has_root() {
+        tracing::warn!(
+            "stripping leading '/' from absolute entry path '{}'",
+            path.display()
+        );
+    }
+}
+
+/// finds the tar entry matching `entry` with a single forward scan of the archive
+fn find_tar_entry<'a, R: Read>(
+    archive: &'a mut tar::Archive<R>,
+    entry: &Entry,
+) -> Result<tar::Entry<'a, R>> {
+    let orig_tar_entry = tar::Archive::entries(archive)?
+        .nth(entry.index())
+        .ok_or(Error::FileNotFound)??;
+    if orig_tar_entry.path()? != entry.path() {
+        Err(Error::FileNotFound)?;
+    }
+    Ok(orig_tar_entry)
+}
 
 struct TarEntries<'a, R: 'a + Read>(Enumerate<::tar::Entries<'a, R>>);
 
@@ -16,62 +186,133 @@ where
     type Item = Result<Entry>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        fn convert<'a, R: 'a + Read>(
-            index: usize,
-            orig_tar_entry: io::Result<tar::Entry<'a, R>>,
-        ) -> Result<Entry> {
-            let orig_tar_entry = orig_tar_entry?;
-            let path = orig_tar_entry.path()?.to_path_buf();
-            let size = orig_tar_entry.size();
-            let entry_type = match orig_tar_entry.header().entry_type() {
-                tar::EntryType::Regular => EntryType::File,
-                tar::EntryType::Directory => EntryType::Directory,
-                _ => EntryType::Other,
-            };
-            let entry = Entry {
-                index,
-                path,
-                size,
-                entry_type,
-            };
-            Ok(entry)
-        }
-
         let (index, orig_tar_entry) = self.0.next()?;
-        Some(convert(index, orig_tar_entry))
+        let entry = orig_tar_entry
+            .map_err(Error::from)
+            .and_then(|orig_tar_entry| to_entry(index, &orig_tar_entry));
+        Some(entry)
     }
 }
 
-impl<R: Read> Archived for tar::Archive<R> {
-    fn unpack(&mut self, dest: &Path) -> Result<()> {
-        Ok(self.unpack(dest)?)
+/// unpacks an entry of a tar archive to the destination directory
+fn extract_entry<R: Read>(archive: &mut tar::Archive<R>, entry: &Entry, dest: &Path) -> Result<()> {
+    warn_if_absolute(entry.path());
+    let outpath = safe_join(dest, entry.path())?;
+    if entry.is_dir() {
+        create_dir_all(&outpath)?;
+        Ok(())
+    } else if entry.is_file() {
+        if let Some(p) = outpath.parent() {
+            if !p.exists() {
+                create_dir_all(p)?;
+            }
+        }
+        find_tar_entry(archive, entry)?.unpack(outpath)?;
+        Ok(())
+    } else if let Some(target) = entry.symlink_target() {
+        unpack_symlink(entry.path(), target, dest)
+    } else if let Some(target) = entry.hardlink_target() {
+        unpack_hardlink(entry.path(), target, dest)
+    } else {
+        Err(Error::EntryTypeNotSupported(entry.entry_type.clone()))
+    }
+}
+
+impl<R: Read> Archived for Tar<R> {
+    fn unpack(&mut self, dest: &Path, on_unpacked: &mut OnUnpacked) -> Result<()> {
+        // NOTE: same as `tar::Archive::unpack`, which also strips the leading
+        //       `/` of absolute paths but silently
+        create_dir_all(dest)?;
+        let dest = &dest.canonicalize().unwrap_or(dest.to_path_buf());
+
+        // directories are unpacked last so that their permissions do not
+        // prevent unpacking the files they contain
+        let mut directories = vec![];
+        for file_in_tar in self.inner.entries()? {
+            let mut file_in_tar = file_in_tar?;
+            let path = file_in_tar.path()?.into_owned();
+            let link_name = file_in_tar.link_name()?.map(Cow::into_owned);
+            warn_if_absolute(&path);
+            // `unpack_in` would silently skip the unsafe entries
+            safe_join(dest, &path)?;
+            let result = match (file_in_tar.header().entry_type(), link_name) {
+                (
+                    tar::EntryType::Regular
+                    | tar::EntryType::Continuous
+                    | tar::EntryType::GNUSparse,
+                    _,
+                ) => file_in_tar.unpack_in(dest).map(|_| ()).map_err(Error::from),
+                // `unpack_in` would accept any target, e.g. `/etc/passwd`
+                (tar::EntryType::Symlink, Some(target)) => unpack_symlink(&path, &target, dest),
+                (tar::EntryType::Link, Some(target)) => unpack_hardlink(&path, &target, dest),
+                (tar::EntryType::Directory, _) => {
+                    directories.push((path, file_in_tar));
+                    continue;
+                }
+                // the other types of entries (e.g. devices) are skipped
+                _ => Ok(()),
+            };
+            result.map_err(|err| err.in_entry(&path))?;
+            on_unpacked();
+        }
+        for (path, mut directory) in directories {
+            directory
+                .unpack_in(dest)
+                .map_err(|err| Error::from(err).in_entry(&path))?;
+            on_unpacked();
+        }
+        Ok(())
     }
 
     fn entries(&mut self) -> Result<Entries> {
-        let inner_entries = tar::Archive::entries(self)?.enumerate();
+        let inner_entries = self.inner.entries()?.enumerate();
         Ok(Box::new(TarEntries(inner_entries)))
     }
 
     fn unpack_entry(&mut self, entry: &Entry, dest: &Path) -> Result<()> {
-        let outpath = dest.join(entry.path());
-        if entry.is_dir() {
-            create_dir_all(&outpath)?;
-        } else if entry.is_file() {
-            if let Some(p) = outpath.parent() {
-                if !p.exists() {
-                    create_dir_all(p)?;
+        extract_entry(&mut self.inner, entry, dest).map_err(|err| err.in_entry(entry.path()))
+    }
+
+    fn walk(&mut self, visit: &mut Visitor) -> Result<()> {
+        for (index, orig_tar_entry) in self.inner.entries()?.enumerate() {
+            let mut orig_tar_entry = orig_tar_entry?;
+            let entry = to_entry(index, &orig_tar_entry)?;
+            if visit(entry, &mut orig_tar_entry)?.is_break() {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    fn entry_reader(&mut self, entry: &Entry) -> Result<Box<dyn Read + '_>> {
+        Ok(Box::new(find_tar_entry(&mut self.inner, entry)?))
+    }
+
+    fn verify_entry(&mut self, entry: &Entry) -> Result<bool> {
+        let mut entries = self.inner.entries()?;
+        let mut header_offset = 0;
+        for _ in 0..entry.index() {
+            let orig_tar_entry = entries.next().ok_or(Error::FileNotFound)??;
+            header_offset = orig_tar_entry.raw_file_position() + padded_size(orig_tar_entry.size());
+        }
+        let header = match entries.next() {
+            Some(Ok(orig_tar_entry)) => {
+                if orig_tar_entry.path()? != entry.path() {
+                    Err(Error::FileNotFound)?;
                 }
+                orig_tar_entry.header().clone()
             }
-            // NOTE: this is terrible, we have to re-lookup the entry
-            //       in the archive...
-            for file_in_tar in tar::Archive::entries(self)? {
-                let mut file_in_tar = file_in_tar?;
-                if file_in_tar.path()? == entry.path() {
-                    file_in_tar.unpack(outpath)?;
-                    return Ok(());
+            // the tar crate refuses to read entries whose header checksum does
+            // not match, the header is then the last block that was read
+            Some(Err(err)) if is_checksum_mismatch(&err) => {
+                match self.last_block.borrow().at(header_offset) {
+                    Some(block) => tar::Header::from_byte_slice(block).clone(),
+                    None => Err(err)?,
                 }
             }
-        }
-        Err(Error::FileNotFound)
+            Some(Err(err)) => Err(err)?,
+            None => Err(Error::FileNotFound)?,
+        };
+        Ok(header.cksum()? == header_checksum(&header))
     }
 }
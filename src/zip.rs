@@ -1,31 +1,297 @@
 use std::{
     fs::{create_dir_all, set_permissions, File, Permissions},
-    io::{self, Read, Seek},
-    path::Path,
+    io::{self, Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+    rc::Rc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
-use zip::{
-    read::ZipFile,
-    result::{ZipError, ZipResult},
-    ZipArchive,
-};
+use zip::{read::ZipFile, result::ZipError, DateTime, ZipArchive};
 
-use crate::{archive::Archived, entry::EntryType, Entries, Entry, Error, Result};
+use crate::{
+    archive::{safe_join, unpack_symlink, Archived, OnUnpacked, Visitor},
+    entry::EntryType,
+    Entries, Entry, Error, Result,
+};
 
 impl From<::zip::result::ZipError> for Error {
     fn from(value: ZipError) -> Self {
         match value {
             ZipError::Io(err) => Error::Io(err),
-            ZipError::InvalidArchive(err) => Error::InvalidArchive(err),
-            ZipError::UnsupportedArchive(err) => Error::UnsupportedArchive(err),
+            ZipError::InvalidArchive(err) => Error::invalid_archive(err),
+            ZipError::UnsupportedArchive(ZipError::PASSWORD_REQUIRED) => Error::PasswordRequired,
+            ZipError::UnsupportedArchive(err) => Error::unsupported_archive(err),
             ZipError::FileNotFound => Error::FileNotFound,
         }
     }
 }
 
+fn u16_at(buf: &[u8], pos: usize) -> u16 {
+    u16::from_le_bytes([buf[pos], buf[pos + 1]])
+}
+
+fn u32_at(buf: &[u8], pos: usize) -> u32 {
+    u32::from_le_bytes([buf[pos], buf[pos + 1], buf[pos + 2], buf[pos + 3]])
+}
+
+/// Locates the central directory using the end of central directory record
+/// and returns its offset, its size and the number of entries it contains.
+/// Zip64 archives are not supported and yield `None`.
+fn central_directory<R: Read + Seek>(reader: &mut R) -> io::Result<Option<(u64, u64, usize)>> {
+    const EOCD_SIGNATURE: &[u8] = b"PK\x05\x06";
+    const EOCD_SIZE: u64 = 22;
+
+    // the end of central directory record is followed by a comment of at most 64k
+    let len = reader.seek(SeekFrom::End(0))?;
+    let tail_start = len.saturating_sub(EOCD_SIZE + u16::MAX as u64);
+    let mut tail = vec![];
+    reader.seek(SeekFrom::Start(tail_start))?;
+    reader.read_to_end(&mut tail)?;
+    let eocd = match tail
+        .windows(EOCD_SIGNATURE.len())
+        .rposition(|w| w == EOCD_SIGNATURE)
+    {
+        Some(eocd) if eocd + EOCD_SIZE as usize <= tail.len() => eocd,
+        _ => return Ok(None),
+    };
+
+    // the central directory is right before the end of central directory record
+    let count = u16_at(&tail, eocd + 10) as usize;
+    let cd_size = u32_at(&tail, eocd + 12) as u64;
+    Ok((tail_start + eocd as u64)
+        .checked_sub(cd_size)
+        .map(|cd_start| (cd_start, cd_size, count)))
+}
+
+/// Reads the host system byte of the "version made by" field of each
+/// entry in the central directory, which is not exposed by the zip crate.
+/// Zip64 archives are not supported and yield an empty list.
+pub(crate) fn read_creator_os<R: Read + Seek>(reader: &mut R) -> io::Result<Vec<u8>> {
+    const CDH_SIGNATURE: &[u8] = b"PK\x01\x02";
+    const CDH_SIZE: usize = 46;
+
+    let (cd_start, cd_size, count) = match central_directory(reader)? {
+        Some(central_directory) => central_directory,
+        None => return Ok(vec![]),
+    };
+    let mut cd = vec![0; cd_size as usize];
+    reader.seek(SeekFrom::Start(cd_start))?;
+    reader.read_exact(&mut cd)?;
+
+    let mut creator_os = Vec::with_capacity(count);
+    let mut pos = 0;
+    while creator_os.len() < count
+        && pos + CDH_SIZE <= cd.len()
+        && &cd[pos..pos + CDH_SIGNATURE.len()] == CDH_SIGNATURE
+    {
+        creator_os.push(cd[pos + 5]);
+        let name_len = u16_at(&cd, pos + 28) as usize;
+        let extra_len = u16_at(&cd, pos + 30) as usize;
+        let comment_len = u16_at(&cd, pos + 32) as usize;
+        pos += CDH_SIZE + name_len + extra_len + comment_len;
+    }
+    Ok(creator_os)
+}
+
+/// Checks that a reader starts with the magic number of Chrome extensions.
+/// The rest of the header is then simply handled by the zip crate which
+/// supports archives prefixed with arbitrary data.
+#[cfg(feature = "crx")]
+pub(crate) fn check_crx_header<R: Read + Seek>(mut reader: R) -> Result<R> {
+    const CRX_MAGIC: &[u8] = b"Cr24";
+
+    let mut magic = [0; 4];
+    reader.read_exact(&mut magic)?;
+    if magic != CRX_MAGIC {
+        Err(Error::invalid_archive("invalid crx header"))?;
+    }
+    reader.seek(SeekFrom::Current(-(CRX_MAGIC.len() as i64)))?;
+    Ok(reader)
+}
+
+/// Converts an MS-DOS date and time, interpreted as UTC, to a system time.
+/// Returns `None` for invalid dates, e.g. zeroed timestamps.
+fn to_system_time(datetime: DateTime) -> Option<SystemTime> {
+    let (year, month, day) = (datetime.year() as i64, datetime.month(), datetime.day());
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+
+    // number of days since 1970-01-01 in the proleptic gregorian calendar
+    let (year, month) = if month <= 2 {
+        (year - 1, month as i64 + 9)
+    } else {
+        (year, month as i64 - 3)
+    };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let day_of_year = (153 * month + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era * 146097 + day_of_era - 719468;
+
+    let seconds = days * 86400
+        + datetime.hour() as i64 * 3600
+        + datetime.minute() as i64 * 60
+        + datetime.second() as i64;
+    Some(UNIX_EPOCH + Duration::from_secs(seconds.try_into().ok()?))
+}
+
+/// file type bits of a unix mode
+const S_IFMT: u32 = 0o170000;
+
+/// file type of a symbolic link in a unix mode
+const S_IFLNK: u32 = 0o120000;
+
+fn to_entry(index: usize, zip_file: &mut ZipFile, creator_os: Option<u8>) -> Result<Entry> {
+    // unsafe paths are only rejected when the entry is unpacked
+    if zip_file.name().contains('\0') {
+        Err(Error::invalid_archive("invalid filename"))?;
+    }
+    let path = PathBuf::from(zip_file.name());
+    let size = zip_file.size();
+    // the zip crate makes up a mode for the entries created on MS-DOS
+    // and Windows, which do not store any
+    let unix_mode = zip_file.unix_mode().filter(|_| creator_os != Some(0));
+    let entry_type = if zip_file.is_dir() {
+        EntryType::Directory
+    } else if unix_mode.is_some_and(|mode| mode & S_IFMT == S_IFLNK) {
+        // the target of a symbolic link is stored as the contents of the entry
+        let mut target = String::new();
+        zip_file.read_to_string(&mut target)?;
+        EntryType::Symlink {
+            target: PathBuf::from(target),
+        }
+    } else {
+        EntryType::File
+    };
+    let entry = Entry {
+        index,
+        path,
+        size,
+        compressed_size: Some(zip_file.compressed_size()),
+        entry_type,
+        creator_os,
+        unix_mode,
+        modified: to_system_time(zip_file.last_modified()),
+        uid: None,
+        gid: None,
+        uname: None,
+        gname: None,
+    };
+    Ok(entry)
+}
+
+/// zip backend, i.e. a zip archive along with the information
+/// that is not exposed by the zip crate.
+pub(crate) struct Zip<R> {
+    inner: ZipArchive<R>,
+    creator_os: Rc<[u8]>,
+}
+
+impl<R: Read + Seek> Zip<R> {
+    /// Opens a zip archive whose host systems, see [`read_creator_os`],
+    /// have already been read.
+    pub(crate) fn new(mut reader: R, creator_os: Rc<[u8]>) -> Result<Self> {
+        reader.rewind()?;
+        let inner = ZipArchive::new(reader)?;
+        Ok(Zip { inner, creator_os })
+    }
+
+    /// Same as [`Zip::new`], reporting how many bytes of the central
+    /// directory have been read by the zip crate while indexing the archive.
+    pub(crate) fn with_index_progress(
+        mut reader: R,
+        creator_os: Rc<[u8]>,
+        on_progress: Rc<IndexProgressFn>,
+    ) -> Result<Zip<IndexProgress<R>>> {
+        let (start, size, _) = central_directory(&mut reader)?.unwrap_or_default();
+        reader.rewind()?;
+        let reader = IndexProgress {
+            inner: reader,
+            pos: 0,
+            start,
+            size,
+            read: 0,
+            on_progress,
+        };
+        let inner = ZipArchive::new(reader)?;
+        Ok(Zip { inner, creator_os })
+    }
+
+    /// unpacks an entry to the destination directory
+    fn extract_entry(&mut self, entry: &Entry, dest: &Path) -> Result<()> {
+        let outpath = safe_join(dest, entry.path())?;
+        if entry.is_dir() {
+            create_dir_all(&outpath)?;
+        } else if entry.is_file() {
+            if let Some(p) = outpath.parent() {
+                if !p.exists() {
+                    create_dir_all(p)?;
+                }
+            }
+            let mut file_in_zip = self.inner.by_index(entry.index())?;
+            let mut outfile = File::create(&outpath)?;
+            io::copy(&mut file_in_zip, &mut outfile)?;
+            if let Some(modified) = entry.last_modified() {
+                outfile.set_modified(modified)?;
+            }
+
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                if let Some(mode) = entry.unix_mode() {
+                    set_permissions(&outpath, Permissions::from_mode(mode))?;
+                }
+            }
+        } else if let Some(target) = entry.symlink_target() {
+            unpack_symlink(entry.path(), target, dest)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// callback called with the number of bytes of the central directory read
+/// so far and its total size
+pub(crate) type IndexProgressFn = dyn Fn(u64, u64);
+
+/// reader counting the bytes read from the central directory of a zip archive
+pub(crate) struct IndexProgress<R> {
+    inner: R,
+    pos: u64,
+    start: u64,
+    size: u64,
+    read: u64,
+    on_progress: Rc<IndexProgressFn>,
+}
+
+impl<R: Read> Read for IndexProgress<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = self.inner.read(buf)?;
+        let end = self.pos + len as u64;
+        let overlap = end
+            .min(self.start + self.size)
+            .saturating_sub(self.pos.max(self.start));
+        self.pos = end;
+        // the entries are read afterwards, they are located before the central directory
+        if overlap > 0 && self.read < self.size {
+            self.read = (self.read + overlap).min(self.size);
+            (self.on_progress)(self.read, self.size);
+        }
+        Ok(len)
+    }
+}
+
+impl<R: Seek> Seek for IndexProgress<R> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.pos = self.inner.seek(pos)?;
+        Ok(self.pos)
+    }
+}
+
 struct ZipEntries<'a, R: 'a> {
     index: usize,
-    archive: &'a mut ZipArchive<R>,
+    archive: &'a mut Zip<R>,
 }
 
 impl<'a, R> Iterator for ZipEntries<'a, R>
@@ -36,40 +302,41 @@ where
     type Item = Result<Entry>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        fn convert(index: usize, zip_file: ZipResult<ZipFile>) -> Result<Entry> {
-            let zip_file = zip_file?;
-            let path = zip_file
-                .enclosed_name()
-                .ok_or(Error::InvalidArchive("invalid filename"))?
-                .to_path_buf();
-            let size = zip_file.size();
-            let entry_type = if zip_file.is_dir() {
-                EntryType::Directory
-            } else {
-                EntryType::File
-            };
-            let entry = Entry {
-                index,
-                path,
-                size,
-                entry_type,
-            };
-            Ok(entry)
-        }
-
-        if self.index < self.archive.len() {
+        if self.index < self.archive.inner.len() {
             let index = self.index;
             self.index += 1;
-            Some(convert(index, self.archive.by_index(index)))
+            let creator_os = self.archive.creator_os.get(index).copied();
+            let entry = self
+                .archive
+                .inner
+                .by_index(index)
+                .map_err(Error::from)
+                .and_then(|mut zip_file| to_entry(index, &mut zip_file, creator_os));
+            Some(entry)
         } else {
             None
         }
     }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        // entries are indexed in the central directory, no need to visit the skipped ones
+        self.index = self.index.saturating_add(n);
+        self.next()
+    }
 }
 
-impl<R: Read + Seek> Archived for ZipArchive<R> {
-    fn unpack(&mut self, dest: &Path) -> Result<()> {
-        Ok(self.extract(dest)?)
+impl<R: Read + Seek> Archived for Zip<R> {
+    fn unpack(&mut self, dest: &Path, on_unpacked: &mut OnUnpacked) -> Result<()> {
+        // NOTE: `ZipArchive::extract` would reject the unsafe entries as an
+        //       invalid archive
+        create_dir_all(dest)?;
+        for index in 0..self.inner.len() {
+            let creator_os = self.creator_os.get(index).copied();
+            let entry = to_entry(index, &mut self.inner.by_index(index)?, creator_os)?;
+            self.unpack_entry(&entry, dest)?;
+            on_unpacked();
+        }
+        Ok(())
     }
 
     fn entries(&mut self) -> Result<Entries> {
@@ -80,28 +347,53 @@ impl<R: Read + Seek> Archived for ZipArchive<R> {
     }
 
     fn unpack_entry(&mut self, entry: &Entry, dest: &Path) -> Result<()> {
-        let outpath = dest.join(entry.path());
-        if entry.is_dir() {
-            create_dir_all(&outpath)?;
-        } else if entry.is_file() {
-            if let Some(p) = outpath.parent() {
-                if !p.exists() {
-                    create_dir_all(p)?;
-                }
+        let result = self.extract_entry(entry, dest);
+        result.map_err(|err| err.in_entry(entry.path()))
+    }
+
+    fn walk(&mut self, visit: &mut Visitor) -> Result<()> {
+        for index in 0..self.inner.len() {
+            let creator_os = self.creator_os.get(index).copied();
+            let mut zip_file = self.inner.by_index(index)?;
+            let entry = to_entry(index, &mut zip_file, creator_os)?;
+            if visit(entry, &mut zip_file)?.is_break() {
+                break;
             }
-            let mut file_in_zip = self.by_index(entry.index())?;
-            let mut outfile = File::create(&outpath)?;
-            io::copy(&mut file_in_zip, &mut outfile)?;
+        }
+        Ok(())
+    }
 
-            #[cfg(unix)]
-            {
-                use std::os::unix::fs::PermissionsExt;
-                if let Some(mode) = file_in_zip.unix_mode() {
-                    set_permissions(&outpath, Permissions::from_mode(mode))?;
+    fn entry_reader(&mut self, entry: &Entry) -> Result<Box<dyn Read + '_>> {
+        Ok(Box::new(self.inner.by_index(entry.index())?))
+    }
+
+    fn verify_entry(&mut self, entry: &Entry) -> Result<bool> {
+        let mut zip_file = self.inner.by_index(entry.index())?;
+        let mut hasher = crc32fast::Hasher::new();
+        let mut buffer = [0; 8192];
+        let mut read = 0;
+        loop {
+            match zip_file.read(&mut buffer) {
+                Ok(0) => break,
+                Ok(count) => {
+                    hasher.update(&buffer[..count]);
+                    read += count as u64;
                 }
+                // the zip crate fails the last read when the CRC-32 does not
+                // match, once all the data of the entry has been returned
+                Err(_) if read == zip_file.size() => break,
+                Err(err) => Err(err)?,
             }
         }
+        Ok(hasher.finalize() == zip_file.crc32())
+    }
 
-        Ok(())
+    fn entry_count_hint(&self) -> Option<usize> {
+        Some(self.inner.len())
+    }
+
+    fn contains(&mut self, path: &Path) -> Result<bool> {
+        // the names are already indexed, no need to read the entries
+        Ok(self.inner.file_names().any(|name| Path::new(name) == path))
     }
 }
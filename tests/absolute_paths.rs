@@ -0,0 +1,45 @@
+#[cfg(feature = "tar")]
+mod absolute_paths {
+    use arkiv::Archive;
+    use std::{fs::read_to_string, path::Path};
+
+    type Error = Box<dyn std::error::Error>;
+    type Result<T> = std::result::Result<T, Error>;
+
+    #[test]
+    fn entries() -> Result<()> {
+        let mut archive = Archive::open("tests/sample/absolute.tar")?;
+        let entries = archive.entry_paths()?;
+        assert_eq!(entries, vec!["/absolute/", "/absolute/absolute.txt"]);
+        Ok(())
+    }
+
+    #[test]
+    fn unpack() -> Result<()> {
+        let sandbox = tempfile::tempdir()?;
+        let mut archive = Archive::open("tests/sample/absolute.tar")?;
+        archive.unpack(&sandbox)?;
+
+        assert!(sandbox.path().join("absolute").is_dir());
+        assert_eq!(
+            read_to_string(sandbox.path().join("absolute/absolute.txt"))?,
+            "absolute\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn unpack_entry() -> Result<()> {
+        let sandbox = tempfile::tempdir()?;
+        let mut archive = Archive::open("tests/sample/absolute.tar")?;
+
+        let file = archive.entry_by_name("/absolute/absolute.txt")?;
+        archive.unpack_entry(&file, &sandbox)?;
+        assert_eq!(
+            read_to_string(sandbox.path().join("absolute/absolute.txt"))?,
+            "absolute\n"
+        );
+        assert!(!Path::new("/absolute").exists());
+        Ok(())
+    }
+}
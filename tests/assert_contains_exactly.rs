@@ -0,0 +1,77 @@
+use arkiv::Archive;
+use std::path::{Path, PathBuf};
+
+type Error = Box<dyn std::error::Error>;
+type Result<T> = std::result::Result<T, Error>;
+
+#[allow(unused)]
+fn test(path: impl AsRef<Path>) -> Result<()> {
+    let mut archive = Archive::open(path)?;
+
+    archive.assert_contains_exactly(&[Path::new("sample/sample.txt"), Path::new("sample")])?;
+
+    let mismatch =
+        archive.assert_contains_exactly(&[Path::new("sample/"), Path::new("sample/other.txt")]);
+    match mismatch {
+        Err(arkiv::Error::ContentMismatch {
+            missing,
+            unexpected,
+        }) => {
+            assert_eq!(missing, vec![PathBuf::from("sample/other.txt")]);
+            assert_eq!(unexpected, vec![PathBuf::from("sample/sample.txt")]);
+        }
+        other => panic!("unexpected result: {other:?}"),
+    }
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "zip")]
+fn zip_archive() -> Result<()> {
+    test("tests/sample/sample.zip")
+}
+
+#[test]
+#[cfg(all(feature = "gzip", feature = "tar"))]
+fn tar_gz_archive() -> Result<()> {
+    test("tests/sample/sample.tar.gz")?;
+    test("tests/sample/sample.tgz")
+}
+
+#[test]
+#[cfg(all(feature = "bzip", feature = "tar"))]
+fn tar_bz2_archive() -> Result<()> {
+    test("tests/sample/sample.tar.bz2")
+}
+
+#[test]
+#[cfg(all(feature = "xz", feature = "tar"))]
+fn tar_xz_archive() -> Result<()> {
+    test("tests/sample/sample.tar.xz")
+}
+
+#[test]
+#[cfg(all(feature = "zstd", feature = "tar"))]
+fn tar_zstd_archive() -> Result<()> {
+    test("tests/sample/sample.tar.zstd")?;
+    test("tests/sample/sample.tar.zst")
+}
+
+#[test]
+#[cfg(feature = "tar")]
+fn gem_archive() -> Result<()> {
+    test("tests/sample/sample.gem")
+}
+
+#[test]
+#[cfg(feature = "crx")]
+fn crx_archive() -> Result<()> {
+    test("tests/sample/sample.crx")
+}
+
+#[test]
+#[cfg(feature = "sevenz")]
+fn sevenz_archive() -> Result<()> {
+    test("tests/sample/sample.7z")
+}
@@ -0,0 +1,235 @@
+#[cfg(any(feature = "zip", feature = "tar"))]
+mod builder {
+    use arkiv::{Archive, ArchiveBuilder, Format};
+    use std::{fs::write, path::Path};
+
+    type Error = Box<dyn std::error::Error>;
+    type Result<T> = std::result::Result<T, Error>;
+
+    fn builder(format: Format, sandbox: &Path) -> Result<ArchiveBuilder> {
+        let src_path = sandbox.join("hello.txt");
+        write(&src_path, "hello\n")?;
+
+        let mut builder = ArchiveBuilder::new(format).with_compression_level(9);
+        builder
+            .add_directory(Path::new("sample/"))?
+            .add_bytes(Path::new("sample/sample.txt"), b"sample\n")?
+            .add_reader(Path::new("sample/reader.txt"), "reader\n".as_bytes())?
+            .add_file(&src_path)?
+            .add_path(Path::new("sample/hello.txt"), &src_path)?;
+        Ok(builder)
+    }
+
+    fn check(mut archive: Archive) -> Result<()> {
+        assert_eq!(
+            archive.entry_paths()?,
+            vec![
+                "sample/",
+                "sample/sample.txt",
+                "sample/reader.txt",
+                "hello.txt",
+                "sample/hello.txt"
+            ]
+        );
+        assert_eq!(
+            archive.read_entry_to_string("sample/sample.txt")?,
+            "sample\n"
+        );
+        assert_eq!(
+            archive.read_entry_to_string("sample/reader.txt")?,
+            "reader\n"
+        );
+        assert_eq!(archive.read_entry_to_string("hello.txt")?, "hello\n");
+        assert_eq!(archive.read_entry_to_string("sample/hello.txt")?, "hello\n");
+        Ok(())
+    }
+
+    #[allow(unused)]
+    fn test(format: Format, file_name: &str) -> Result<()> {
+        let sandbox = tempfile::tempdir()?;
+        let builder = builder(format.clone(), sandbox.path())?;
+
+        let dest = sandbox.path().join(file_name);
+        builder.finish(&dest)?;
+        check(Archive::open(&dest)?)?;
+
+        let bytes = builder.finish_to_bytes()?;
+        check(Archive::from_bytes(bytes, format.clone())?)?;
+
+        if format == Format::Zip {
+            // zip archives cannot be written in chunks, see `zip_archive_in_chunks`
+            return Ok(());
+        }
+        let mut bytes = vec![];
+        let mut chunk_sizes = vec![];
+        builder.finish_in_chunks(100, |offset, chunk| {
+            assert_eq!(offset, bytes.len() as u64);
+            bytes.extend_from_slice(chunk);
+            chunk_sizes.push(chunk.len());
+        })?;
+        let (last, others) = chunk_sizes.split_last().unwrap();
+        assert!(others.iter().all(|size| *size == 100));
+        assert!((1..=100).contains(last));
+        check(Archive::from_bytes(bytes, format)?)?;
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "zip")]
+    fn zip_archive() -> Result<()> {
+        test(Format::Zip, "sample.zip")
+    }
+
+    #[test]
+    #[cfg(feature = "zip")]
+    fn zip_archive_in_chunks() -> Result<()> {
+        let sandbox = tempfile::tempdir()?;
+        let builder = builder(Format::Zip, sandbox.path())?;
+        let mut called = false;
+        let res = builder.finish_in_chunks(100, |_, _| called = true);
+        assert!(matches!(res, Err(arkiv::Error::UnsupportedArchive { .. })));
+        assert!(!called);
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "tar")]
+    fn tar_archive() -> Result<()> {
+        test(Format::Tar, "sample.tar")
+    }
+
+    #[test]
+    #[cfg(all(feature = "gzip", feature = "tar"))]
+    fn tar_gz_archive() -> Result<()> {
+        test(Format::TarGzip, "sample.tar.gz")
+    }
+
+    #[test]
+    #[cfg(all(feature = "bzip", feature = "tar"))]
+    fn tar_bz2_archive() -> Result<()> {
+        test(Format::TarBzip2, "sample.tar.bz2")
+    }
+
+    #[test]
+    #[cfg(all(feature = "xz", feature = "tar"))]
+    fn tar_xz_archive() -> Result<()> {
+        test(Format::TarXz2, "sample.tar.xz")
+    }
+
+    #[test]
+    #[cfg(all(feature = "zstd", feature = "tar"))]
+    fn tar_zstd_archive() -> Result<()> {
+        test(Format::TarZstd, "sample.tar.zst")
+    }
+
+    #[test]
+    #[cfg(feature = "zip")]
+    fn zip_archive_without_compression() -> Result<()> {
+        let sandbox = tempfile::tempdir()?;
+        let builder = builder(Format::Zip, sandbox.path())?.no_compression();
+        assert_eq!(builder.format(), Format::Zip);
+
+        let mut archive = Archive::from_bytes(builder.finish_to_bytes()?, Format::Zip)?;
+        let entry = archive.entry_by_name("sample/sample.txt")?;
+        assert_eq!(entry.compressed_size(), Some(entry.size()));
+        check(archive)
+    }
+
+    #[test]
+    #[cfg(all(feature = "gzip", feature = "tar"))]
+    fn tar_gz_archive_without_compression() -> Result<()> {
+        let sandbox = tempfile::tempdir()?;
+        let builder = builder(Format::TarGzip, sandbox.path())?
+            .with_compression(arkiv::CompressionMode::None);
+        assert_eq!(builder.format(), Format::Tar);
+
+        let bytes = builder.finish_to_bytes()?;
+        assert_eq!(Format::infer_from_magic_bytes(&bytes), Format::Tar);
+        check(Archive::from_bytes(bytes, Format::Tar)?)
+    }
+
+    #[test]
+    fn unsupported_format() -> Result<()> {
+        let sandbox = tempfile::tempdir()?;
+        let builder = builder(Format::Deb, sandbox.path())?;
+        assert!(matches!(
+            builder.finish_to_bytes(),
+            Err(arkiv::Error::UnsupportedArchive { .. })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "tar")]
+    fn missing_parent_directory() -> Result<()> {
+        let sandbox = tempfile::tempdir()?;
+        let dest = sandbox.path().join("missing/parent/sample.tar");
+        let builder = builder(Format::Tar, sandbox.path())?;
+
+        match builder.finish(&dest) {
+            Err(arkiv::Error::Io(err)) => {
+                assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+                assert!(err.to_string().contains("missing/parent"));
+            }
+            other => panic!("unexpected result: {other:?}"),
+        }
+        assert!(!sandbox.path().join("missing").exists());
+
+        builder.with_create_parents(true).finish(&dest)?;
+        assert!(dest.is_file());
+        Ok(())
+    }
+
+    #[test]
+    fn add_path_not_a_file() -> Result<()> {
+        let sandbox = tempfile::tempdir()?;
+        let mut builder = ArchiveBuilder::new(Format::Tar);
+        let res = builder.add_path(Path::new("sample/"), sandbox.path());
+        assert!(matches!(res, Err(arkiv::Error::Io(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_entry_name() {
+        let mut builder = ArchiveBuilder::new(Format::Tar);
+        assert!(builder.add_bytes(Path::new("/etc/passwd"), b"").is_err());
+        assert!(builder.add_bytes(Path::new("../outside"), b"").is_err());
+        assert!(builder.add_directory(Path::new("")).is_err());
+    }
+}
+
+#[cfg(all(feature = "zstd", feature = "tar"))]
+mod zstd_dictionary {
+    use arkiv::{Archive, ArchiveBuilder, ArchiveOptions, Format};
+    use std::path::Path;
+
+    type Error = Box<dyn std::error::Error>;
+    type Result<T> = std::result::Result<T, Error>;
+
+    #[test]
+    fn tar_zstd_archive() -> Result<()> {
+        let dict = b"sample sample sample sample sample sample sample sample".repeat(4);
+        let mut builder = ArchiveBuilder::new(Format::TarZstd).with_zstd_dictionary(&dict);
+        builder.add_bytes(Path::new("sample/sample.txt"), b"sample\n")?;
+        let sandbox = tempfile::tempdir()?;
+        let path = sandbox.path().join("sample.tar.zst");
+        builder.finish(&path)?;
+
+        // the dictionary is needed to decompress the archive
+        let mut archive = Archive::open(&path)?;
+        assert!(archive.entry_paths().is_err());
+
+        let options = ArchiveOptions {
+            zstd_dictionary: Some(dict),
+            ..Default::default()
+        };
+        let mut archive = Archive::open(&path)?.with_options(options);
+        assert_eq!(archive.entry_paths()?, vec!["sample/sample.txt"]);
+        assert_eq!(
+            archive.read_entry_to_string("sample/sample.txt")?,
+            "sample\n"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,67 @@
+use arkiv::Archive;
+use std::{fs::read_to_string, path::Path};
+
+type Error = Box<dyn std::error::Error>;
+type Result<T> = std::result::Result<T, Error>;
+
+#[allow(unused)]
+fn test(path: impl AsRef<Path>) -> Result<()> {
+    let sandbox = tempfile::tempdir()?;
+    let mut archive = Archive::open(path)?;
+
+    assert_eq!(archive.entry_paths()?, vec!["sample.txt"]);
+    assert_eq!(archive.entry_count_hint()?, Some(1));
+
+    archive.unpack(&sandbox)?;
+    assert_eq!(
+        read_to_string(sandbox.path().join("sample.txt"))?,
+        "sample\n"
+    );
+
+    let entry = archive.entry_by_name("sample.txt")?;
+    assert!(entry.is_file());
+    assert!(archive.verify_entry(&entry)?);
+    assert_eq!(archive.read_entry_to_string("sample.txt")?, "sample\n");
+
+    let not_found = archive.read_entry_to_bytes("sample.txt.gz");
+    assert!(matches!(not_found, Err(arkiv::Error::FileNotFound)));
+
+    let dest = sandbox.path().join("decompressed.txt");
+    assert_eq!(Archive::decompress(archive.path(), &dest)?, 7);
+    assert_eq!(read_to_string(&dest)?, "sample\n");
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "gzip")]
+fn gz_file() -> Result<()> {
+    test("tests/sample/sample.txt.gz")
+}
+
+#[test]
+#[cfg(feature = "bzip")]
+fn bz2_file() -> Result<()> {
+    test("tests/sample/sample.txt.bz2")
+}
+
+#[test]
+#[cfg(feature = "xz")]
+fn xz_file() -> Result<()> {
+    test("tests/sample/sample.txt.xz")
+}
+
+#[test]
+#[cfg(feature = "zstd")]
+fn zst_file() -> Result<()> {
+    test("tests/sample/sample.txt.zst")
+}
+
+#[test]
+#[cfg(all(feature = "gzip", feature = "tar"))]
+fn decompress_archive() -> Result<()> {
+    let sandbox = tempfile::tempdir()?;
+    let res = Archive::decompress("tests/sample/sample.tar.gz", sandbox.path().join("sample"));
+    assert!(matches!(res, Err(arkiv::Error::UnsupportedArchive { .. })));
+    Ok(())
+}
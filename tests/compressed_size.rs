@@ -0,0 +1,75 @@
+use arkiv::Archive;
+use std::path::Path;
+
+type Error = Box<dyn std::error::Error>;
+type Result<T> = std::result::Result<T, Error>;
+
+#[allow(unused)]
+fn test(path: impl AsRef<Path>, expected: Option<u64>) -> Result<()> {
+    let mut archive = Archive::open(path)?;
+    let entry = archive.entry_by_name("sample/sample.txt")?;
+    assert_eq!(entry.compressed_size(), expected);
+    assert_eq!(entry.compression_ratio(), expected.map(|_| 1.0));
+
+    // the size of directories is zero
+    let entry = archive.entry_by_name("sample/")?;
+    assert_eq!(entry.compression_ratio(), None);
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "zip")]
+fn zip_archive() -> Result<()> {
+    // the sample file is too small to be compressed
+    test("tests/sample/sample.zip", Some(7))?;
+
+    let mut archive = Archive::open("tests/sample/large-entry.zip")?;
+    let entry = archive.entry_by_name("large/data.bin")?;
+    assert_eq!(entry.compressed_size(), Some(16589));
+    assert_eq!(entry.compression_ratio(), Some(16589.0 / 4194304.0));
+    Ok(())
+}
+
+#[test]
+#[cfg(all(feature = "gzip", feature = "tar"))]
+fn tar_gz_archive() -> Result<()> {
+    test("tests/sample/sample.tar.gz", None)?;
+    test("tests/sample/sample.tgz", None)
+}
+
+#[test]
+#[cfg(all(feature = "bzip", feature = "tar"))]
+fn tar_bz2_archive() -> Result<()> {
+    test("tests/sample/sample.tar.bz2", None)
+}
+
+#[test]
+#[cfg(all(feature = "xz", feature = "tar"))]
+fn tar_xz_archive() -> Result<()> {
+    test("tests/sample/sample.tar.xz", None)
+}
+
+#[test]
+#[cfg(all(feature = "zstd", feature = "tar"))]
+fn tar_zstd_archive() -> Result<()> {
+    test("tests/sample/sample.tar.zstd", None)?;
+    test("tests/sample/sample.tar.zst", None)
+}
+
+#[test]
+#[cfg(feature = "tar")]
+fn gem_archive() -> Result<()> {
+    test("tests/sample/sample.gem", None)
+}
+
+#[test]
+#[cfg(feature = "crx")]
+fn crx_archive() -> Result<()> {
+    test("tests/sample/sample.crx", Some(7))
+}
+
+#[test]
+#[cfg(feature = "sevenz")]
+fn sevenz_archive() -> Result<()> {
+    test("tests/sample/sample.7z", None)
+}
@@ -0,0 +1,65 @@
+use arkiv::Archive;
+use std::path::Path;
+
+type Error = Box<dyn std::error::Error>;
+type Result<T> = std::result::Result<T, Error>;
+
+#[allow(unused)]
+fn test(path: impl AsRef<Path>, expected: Option<u8>) -> Result<()> {
+    let mut archive = Archive::open(path)?;
+    for entry in archive.entries_iter()? {
+        assert_eq!(entry?.creator_os(), expected);
+    }
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "zip")]
+fn zip_archive() -> Result<()> {
+    test("tests/sample/sample.zip", Some(3))?;
+    test("tests/sample/scripts.zip", Some(0))
+}
+
+#[test]
+#[cfg(all(feature = "gzip", feature = "tar"))]
+fn tar_gz_archive() -> Result<()> {
+    test("tests/sample/sample.tar.gz", None)?;
+    test("tests/sample/sample.tgz", None)
+}
+
+#[test]
+#[cfg(all(feature = "bzip", feature = "tar"))]
+fn tar_bz2_archive() -> Result<()> {
+    test("tests/sample/sample.tar.bz2", None)
+}
+
+#[test]
+#[cfg(all(feature = "xz", feature = "tar"))]
+fn tar_xz_archive() -> Result<()> {
+    test("tests/sample/sample.tar.xz", None)
+}
+
+#[test]
+#[cfg(all(feature = "zstd", feature = "tar"))]
+fn tar_zstd_archive() -> Result<()> {
+    test("tests/sample/sample.tar.zstd", None)?;
+    test("tests/sample/sample.tar.zst", None)
+}
+
+#[test]
+#[cfg(feature = "tar")]
+fn gem_archive() -> Result<()> {
+    test("tests/sample/sample.gem", None)
+}
+
+#[test]
+#[cfg(feature = "crx")]
+fn crx_archive() -> Result<()> {
+    test("tests/sample/sample.crx", Some(3))
+}
+
+#[test]
+#[cfg(feature = "sevenz")]
+fn sevenz_archive() -> Result<()> {
+    test("tests/sample/sample.7z", None)
+}
@@ -0,0 +1,81 @@
+use arkiv::Archive;
+use std::{io::read_to_string, path::Path};
+
+type Error = Box<dyn std::error::Error>;
+type Result<T> = std::result::Result<T, Error>;
+
+#[allow(unused)]
+fn test(path: impl AsRef<Path>) -> Result<()> {
+    let mut archive = Archive::open(path)?;
+    let mut cursor = archive.cursor()?;
+
+    assert!(cursor.current().is_none());
+    assert!(cursor.prev()?.is_none());
+
+    let entry = cursor.seek_to(1)?.expect("entry at index 1");
+    assert_eq!(entry.path(), Path::new("sample/sample.txt"));
+    assert_eq!(read_to_string(cursor.read_current()?)?, "sample\n");
+
+    let entry = cursor.prev()?.expect("entry at index 0");
+    assert!(entry.is_dir());
+    assert!(cursor.prev()?.is_none());
+
+    let entry = cursor.next()?.expect("entry at index 1");
+    assert_eq!(entry.path(), Path::new("sample/sample.txt"));
+    assert!(cursor.next()?.is_none());
+    assert!(cursor.seek_to(42)?.is_none());
+
+    let current = cursor.current().expect("cursor left unchanged");
+    assert_eq!(current.path(), Path::new("sample/sample.txt"));
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "zip")]
+fn zip_archive() -> Result<()> {
+    test("tests/sample/sample.zip")
+}
+
+#[test]
+#[cfg(all(feature = "gzip", feature = "tar"))]
+fn tar_gz_archive() -> Result<()> {
+    test("tests/sample/sample.tar.gz")?;
+    test("tests/sample/sample.tgz")
+}
+
+#[test]
+#[cfg(all(feature = "bzip", feature = "tar"))]
+fn tar_bz2_archive() -> Result<()> {
+    test("tests/sample/sample.tar.bz2")
+}
+
+#[test]
+#[cfg(all(feature = "xz", feature = "tar"))]
+fn tar_xz_archive() -> Result<()> {
+    test("tests/sample/sample.tar.xz")
+}
+
+#[test]
+#[cfg(all(feature = "zstd", feature = "tar"))]
+fn tar_zstd_archive() -> Result<()> {
+    test("tests/sample/sample.tar.zstd")?;
+    test("tests/sample/sample.tar.zst")
+}
+
+#[test]
+#[cfg(feature = "tar")]
+fn gem_archive() -> Result<()> {
+    test("tests/sample/sample.gem")
+}
+
+#[test]
+#[cfg(feature = "crx")]
+fn crx_archive() -> Result<()> {
+    test("tests/sample/sample.crx")
+}
+
+#[test]
+#[cfg(feature = "sevenz")]
+fn sevenz_archive() -> Result<()> {
+    test("tests/sample/sample.7z")
+}
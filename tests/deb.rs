@@ -0,0 +1,62 @@
+#[cfg(feature = "deb")]
+mod deb {
+    use arkiv::{Archive, DebPackage, Error as ArkivError, Format};
+    use std::{fs::read_to_string, path::Path};
+
+    type Error = Box<dyn std::error::Error>;
+    type Result<T> = std::result::Result<T, Error>;
+
+    #[test]
+    fn deb_archive() -> Result<()> {
+        let mut archive = Archive::open("tests/sample/sample.deb")?;
+        assert_eq!(archive.format(), &Format::Deb);
+        assert_eq!(
+            archive.entry_paths()?,
+            vec!["debian-binary", "control.tar.gz", "data.tar.xz"]
+        );
+
+        let sandbox = tempfile::tempdir()?;
+        archive.unpack(&sandbox)?;
+        assert_eq!(
+            read_to_string(sandbox.path().join("debian-binary"))?,
+            "2.0\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(all(feature = "tar", feature = "gzip"))]
+    fn deb_control() -> Result<()> {
+        let package = DebPackage::open("tests/sample/sample.deb")?;
+        let mut control = package.control()?;
+        assert_eq!(control.format(), &Format::TarGzip);
+        assert!(control.entry_by_name("./control").is_ok());
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(all(feature = "tar", feature = "xz"))]
+    fn deb_data() -> Result<()> {
+        let package = DebPackage::open("tests/sample/sample.deb")?;
+        let mut data = package.data()?;
+        assert_eq!(data.format(), &Format::TarXz2);
+
+        let sandbox = tempfile::tempdir()?;
+        data.unpack(&sandbox)?;
+        assert_eq!(
+            read_to_string(sandbox.path().join("sample/sample.txt"))?,
+            "sample\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn not_a_deb_package() -> Result<()> {
+        let res = DebPackage::open("tests/sample/sample.gem");
+        assert!(matches!(res, Err(ArkivError::InvalidArchive { .. })));
+
+        let res = DebPackage::open(Path::new("tests/sample/not_found.deb"));
+        assert!(matches!(res, Err(ArkivError::ArchiveNotFound(_))));
+        Ok(())
+    }
+}
@@ -0,0 +1,68 @@
+use arkiv::Archive;
+use std::{collections::BTreeMap, path::Path, path::PathBuf};
+
+type Error = Box<dyn std::error::Error>;
+type Result<T> = std::result::Result<T, Error>;
+
+#[allow(unused)]
+fn test(path: impl AsRef<Path>) -> Result<()> {
+    let mut archive = Archive::open(path)?;
+    let actual = archive.directory_sizes()?;
+    let expected = BTreeMap::from([(PathBuf::from("sample"), 7)]);
+
+    /// call a second time to check that the rewind is done properly
+    assert!(archive.directory_sizes().is_ok());
+
+    assert_eq!(actual, expected);
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "zip")]
+fn zip_archive() -> Result<()> {
+    test("tests/sample/sample.zip")
+}
+
+#[test]
+#[cfg(all(feature = "gzip", feature = "tar"))]
+fn tar_gz_archive() -> Result<()> {
+    test("tests/sample/sample.tar.gz")?;
+    test("tests/sample/sample.tgz")
+}
+
+#[test]
+#[cfg(all(feature = "bzip", feature = "tar"))]
+fn tar_bz2_archive() -> Result<()> {
+    test("tests/sample/sample.tar.bz2")
+}
+
+#[test]
+#[cfg(all(feature = "xz", feature = "tar"))]
+fn tar_xz_archive() -> Result<()> {
+    test("tests/sample/sample.tar.xz")
+}
+
+#[test]
+#[cfg(all(feature = "zstd", feature = "tar"))]
+fn tar_zstd_archive() -> Result<()> {
+    test("tests/sample/sample.tar.zstd")?;
+    test("tests/sample/sample.tar.zst")
+}
+
+#[test]
+#[cfg(feature = "tar")]
+fn gem_archive() -> Result<()> {
+    test("tests/sample/sample.gem")
+}
+
+#[test]
+#[cfg(feature = "crx")]
+fn crx_archive() -> Result<()> {
+    test("tests/sample/sample.crx")
+}
+
+#[test]
+#[cfg(feature = "sevenz")]
+fn sevenz_archive() -> Result<()> {
+    test("tests/sample/sample.7z")
+}
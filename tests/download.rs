@@ -1,12 +1,20 @@
 #[cfg(feature = "download")]
 mod download {
 
-    use arkiv::{Archive, Downloader, Error as ArkivError};
-    use httptest::{matchers::request, responders::status_code, Expectation, Server};
+    use arkiv::{Archive, Downloader, Error as ArkivError, Format};
+    use flate2::{write::GzEncoder, Compression};
+    use httptest::{
+        all_of,
+        matchers::{contains, request},
+        responders::{cycle, status_code},
+        Expectation, Server, ServerBuilder,
+    };
     use std::{
         fs::{self, File},
-        io::{BufReader, Read},
-        path::Path,
+        io::{BufReader, Read, Write},
+        path::{Path, PathBuf},
+        sync::{Arc, Mutex},
+        time::Duration,
     };
 
     type Error = Box<dyn std::error::Error>;
@@ -76,12 +84,353 @@ mod download {
         assert!(callbacks.last().unwrap() == &(total_filesize, total_filesize));
 
         // check the contents
-        let mut actual = archive.entries()?;
+        let mut actual = archive.entry_paths()?;
+        let mut expected = vec!["sample/", "sample/sample.txt"];
+        actual.sort();
+        expected.sort();
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[allow(unused)]
+    async fn test_persistent(path: impl AsRef<Path>) -> Result<()> {
+        // read archive contents into buffer
+        let archive_file = File::open(path.as_ref())?;
+        let mut reader = BufReader::new(archive_file);
+        let mut buffer = Vec::new();
+        reader.read_to_end(&mut buffer)?;
+
+        // prepare test server to return archive contents on request
+        let server = Server::run();
+        server.expect(
+            Expectation::matching(request::method_path(
+                "GET",
+                format!("/{}", path.as_ref().display()),
+            ))
+            .respond_with(status_code(200).body(buffer)),
+        );
+
+        // download archive
+        let url = format!("/{}", path.as_ref().display());
+        let url = server.url(&url);
+        let (mut archive, downloaded) = Downloader::new()
+            .url(url.to_string())
+            .to_temp_persistent()
+            .download()?;
+
+        // check the contents
+        let mut actual = archive.entry_paths()?;
+        let mut expected = vec!["sample/", "sample/sample.txt"];
+        actual.sort();
+        expected.sort();
+        assert_eq!(actual, expected);
+
+        // check the downloaded file outlives the archive
+        drop(archive);
+        assert!(downloaded.exists());
+        fs::remove_dir_all(downloaded.parent().unwrap())?;
+
+        Ok(())
+    }
+
+    #[allow(unused)]
+    async fn test_on_complete(path: impl AsRef<Path>) -> Result<()> {
+        // read archive contents into buffer
+        let archive_file = File::open(path.as_ref())?;
+        let mut reader = BufReader::new(archive_file);
+        let mut buffer = Vec::new();
+        reader.read_to_end(&mut buffer)?;
+
+        // prepare test server to return archive contents on request
+        let server = Server::run();
+        server.expect(
+            Expectation::matching(request::method_path(
+                "GET",
+                format!("/{}", path.as_ref().display()),
+            ))
+            .respond_with(status_code(200).body(buffer)),
+        );
+
+        // download archive
+        let url = format!("/{}", path.as_ref().display());
+        let url = server.url(&url);
+        let completed: Arc<Mutex<Vec<PathBuf>>> = Arc::default();
+        let archive = Downloader::new()
+            .url(url.to_string())
+            .to_temp()
+            .on_complete({
+                let completed = completed.clone();
+                move |archive| completed.lock().unwrap().push(archive.path().to_path_buf())
+            })
+            .download()?;
+
+        // check the callback was called once with the returned archive
+        assert_eq!(
+            *completed.lock().unwrap(),
+            vec![archive.path().to_path_buf()]
+        );
+
+        Ok(())
+    }
+
+    #[allow(unused)]
+    async fn test_content_encoding(path: impl AsRef<Path>) -> Result<()> {
+        // read archive contents into buffer
+        let archive_file = File::open(path.as_ref())?;
+        let mut reader = BufReader::new(archive_file);
+        let mut buffer = Vec::new();
+        reader.read_to_end(&mut buffer)?;
+
+        // compress the response body, independently of the archive format
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&buffer)?;
+        let body = encoder.finish()?;
+
+        // prepare test server to return compressed archive contents on request
+        let server = Server::run();
+        server.expect(
+            Expectation::matching(request::method_path(
+                "GET",
+                format!("/{}", path.as_ref().display()),
+            ))
+            .respond_with(
+                status_code(200)
+                    .insert_header("content-encoding", "gzip")
+                    .body(body),
+            ),
+        );
+
+        // download archive
+        let url = format!("/{}", path.as_ref().display());
+        let url = server.url(&url);
+        let mut archive = Archive::download(url.to_string())?;
+
+        // check the raw archive was stored
+        assert_eq!(fs::read(archive.path())?, buffer);
+        assert_eq!(archive.entry_paths()?.len(), 2);
+
+        Ok(())
+    }
+
+    #[allow(unused)]
+    async fn test_redirects(path: impl AsRef<Path>) -> Result<()> {
+        // read archive contents into buffer
+        let archive_file = File::open(path.as_ref())?;
+        let mut reader = BufReader::new(archive_file);
+        let mut buffer = Vec::new();
+        reader.read_to_end(&mut buffer)?;
+
+        // prepare test servers to redirect to the archive, on the same host
+        // or on another host served by a second server
+        let server = Server::run();
+        let other_server = ServerBuilder::new()
+            .bind_addr(([127, 0, 0, 1], 0).into())
+            .run()?;
+        let archive_path = format!("/{}", path.as_ref().display());
+        let other_host = match server.addr().is_ipv4() {
+            true => "localhost",
+            false => "127.0.0.1",
+        };
+        let other_host = format!(
+            "http://{other_host}:{}{archive_path}",
+            other_server.addr().port()
+        );
+        server.expect(
+            Expectation::matching(request::method_path("GET", archive_path.clone()))
+                .times(1)
+                .respond_with(status_code(200).body(buffer.clone())),
+        );
+        other_server.expect(
+            Expectation::matching(request::method_path("GET", archive_path.clone()))
+                .times(1)
+                .respond_with(status_code(200).body(buffer)),
+        );
+        server.expect(
+            Expectation::matching(request::method_path("GET", "/same/sample.zip"))
+                .times(1)
+                .respond_with(status_code(302).insert_header("location", archive_path)),
+        );
+        server.expect(
+            Expectation::matching(request::method_path("GET", "/other/sample.zip"))
+                .times(2)
+                .respond_with(status_code(301).insert_header("location", other_host)),
+        );
+
+        // redirects to the same host are followed
+        let url = server.url("/same/sample.zip");
+        let archive = Downloader::new()
+            .url(url.to_string())
+            .to_temp()
+            .with_restrict_redirects_to_same_host(true)
+            .download();
+        assert!(archive.is_ok());
+
+        // redirects to another host are refused
+        let url = server.url("/other/sample.zip");
+        let archive = Downloader::new()
+            .url(url.to_string())
+            .to_temp()
+            .with_restrict_redirects_to_same_host(true)
+            .download();
+        assert!(matches!(archive, Err(ArkivError::InvalidRequest(_))));
+
+        // unless the restriction is disabled
+        let archive = Downloader::new()
+            .url(url.to_string())
+            .to_temp()
+            .with_restrict_redirects_to_same_host(false)
+            .download();
+        assert!(archive.is_ok());
+
+        Ok(())
+    }
+
+    #[allow(unused)]
+    async fn test_bearer_token(path: impl AsRef<Path>) -> Result<()> {
+        // read archive contents into buffer
+        let archive_file = File::open(path.as_ref())?;
+        let mut reader = BufReader::new(archive_file);
+        let mut buffer = Vec::new();
+        reader.read_to_end(&mut buffer)?;
+
+        // prepare test server to only return archive contents to authorized requests
+        let server = Server::run();
+        server.expect(
+            Expectation::matching(all_of![
+                request::method_path("GET", format!("/{}", path.as_ref().display())),
+                request::headers(contains(("authorization", "Bearer s3cr3t"))),
+            ])
+            .respond_with(status_code(200).body(buffer)),
+        );
+        let url = format!("/{}", path.as_ref().display());
+        let url = server.url(&url);
+
+        // the variable is only read when downloading
+        let downloader = Downloader::new()
+            .url(url.to_string())
+            .to_temp()
+            .with_bearer_token_from_env("ARKIV_TEST_TOKEN");
+        std::env::set_var("ARKIV_TEST_TOKEN", "s3cr3t");
+        let archive = downloader.download();
+        assert!(archive.is_ok());
+
+        // missing variable
+        std::env::remove_var("ARKIV_TEST_TOKEN");
+        let archive = Downloader::new()
+            .url(url.to_string())
+            .to_temp()
+            .with_bearer_token_from_env("ARKIV_TEST_TOKEN")
+            .download();
+        assert!(
+            matches!(archive, Err(ArkivError::MissingEnvVar(name)) if name == "ARKIV_TEST_TOKEN")
+        );
+
+        Ok(())
+    }
+
+    #[allow(unused)]
+    async fn test_retries(path: impl AsRef<Path>) -> Result<()> {
+        // read archive contents into buffer
+        let archive_file = File::open(path.as_ref())?;
+        let mut reader = BufReader::new(archive_file);
+        let mut buffer = Vec::new();
+        reader.read_to_end(&mut buffer)?;
+
+        // prepare test server to fail twice before returning archive contents
+        let server = Server::run();
+        server.expect(
+            Expectation::matching(request::method_path(
+                "GET",
+                format!("/{}", path.as_ref().display()),
+            ))
+            .times(3)
+            .respond_with(cycle![
+                status_code(503),
+                status_code(503),
+                status_code(200).body(buffer),
+            ]),
+        );
+        let url = format!("/{}", path.as_ref().display());
+        let url = server.url(&url);
+
+        let mut callbacks: Vec<(u64, u64)> = vec![];
+        let archive = Downloader::new()
+            .url(url.to_string())
+            .to_temp()
+            .on_progress(|current, total| callbacks.push((current, total)))
+            .retries(2)
+            .retry_backoff(Duration::from_millis(10))
+            .download();
+        assert!(archive.is_ok());
+        assert_eq!(callbacks.first().map(|(current, _)| *current), Some(0));
+
+        Ok(())
+    }
+
+    #[allow(unused)]
+    async fn test_not_enough_retries(path: impl AsRef<Path>) -> Result<()> {
+        // prepare test server to always fail
+        let server = Server::run();
+        server.expect(
+            Expectation::matching(request::method_path(
+                "GET",
+                format!("/{}", path.as_ref().display()),
+            ))
+            .times(2)
+            .respond_with(status_code(503)),
+        );
+        let url = format!("/{}", path.as_ref().display());
+        let url = server.url(&url);
+
+        let res = Downloader::new()
+            .url(url.to_string())
+            .to_temp()
+            .retries(1)
+            .retry_backoff(Duration::from_millis(10))
+            .download();
+        assert!(matches!(res, Err(ArkivError::InvalidRequest(_))));
+
+        Ok(())
+    }
+
+    #[allow(unused)]
+    async fn test_stream(path: impl AsRef<Path>, format: Format) -> Result<()> {
+        // read archive contents into buffer
+        let archive_file = File::open(path.as_ref())?;
+        let mut reader = BufReader::new(archive_file);
+        let mut buffer = Vec::new();
+        reader.read_to_end(&mut buffer)?;
+
+        // prepare test server to return archive contents on each request
+        let server = Server::run();
+        server.expect(
+            Expectation::matching(request::method_path(
+                "GET",
+                format!("/{}", path.as_ref().display()),
+            ))
+            .times(2)
+            .respond_with(status_code(200).body(buffer)),
+        );
+
+        // stream archive, once to list its contents and once to unpack it
+        let url = format!("/{}", path.as_ref().display());
+        let url = server.url(&url);
+        let mut archive = Archive::open_stream(&url.to_string(), format)?;
+
+        let mut actual = archive.entry_paths()?;
         let mut expected = vec!["sample/", "sample/sample.txt"];
         actual.sort();
         expected.sort();
         assert_eq!(actual, expected);
 
+        let sandbox = tempfile::tempdir()?;
+        archive.unpack(&sandbox)?;
+        assert_eq!(
+            fs::read_to_string(sandbox.path().join("sample/sample.txt"))?,
+            "sample\n"
+        );
+
         Ok(())
     }
 
@@ -109,7 +458,7 @@ mod download {
         let mut archive = Archive::download(url.to_string())?;
 
         // check the contents
-        let mut actual = archive.entries()?;
+        let mut actual = archive.entry_paths()?;
         let mut expected = vec!["sample/", "sample/sample.txt"];
         actual.sort();
         expected.sort();
@@ -129,6 +478,60 @@ mod download {
         test_progress("tests/sample/sample.zip").await
     }
 
+    #[tokio::test]
+    #[cfg(feature = "zip")]
+    async fn download_persistent() -> Result<()> {
+        test_persistent("tests/sample/sample.zip").await
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "zip")]
+    async fn download_on_complete() -> Result<()> {
+        test_on_complete("tests/sample/sample.zip").await
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "zip")]
+    async fn download_content_encoding() -> Result<()> {
+        test_content_encoding("tests/sample/sample.zip").await
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "zip")]
+    async fn download_redirects() -> Result<()> {
+        test_redirects("tests/sample/sample.zip").await
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "zip")]
+    async fn download_bearer_token() -> Result<()> {
+        test_bearer_token("tests/sample/sample.zip").await
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "zip")]
+    async fn download_retries() -> Result<()> {
+        test_retries("tests/sample/sample.zip").await
+    }
+
+    #[tokio::test]
+    async fn download_not_enough_retries() -> Result<()> {
+        test_not_enough_retries("tests/sample/sample.zip").await
+    }
+
+    #[tokio::test]
+    #[cfg(all(feature = "gzip", feature = "tar"))]
+    async fn stream_tar_gz_archive() -> Result<()> {
+        test_stream("tests/sample/sample.tar.gz", Format::TarGzip).await
+    }
+
+    #[tokio::test]
+    async fn stream_zip_archive() -> Result<()> {
+        let res = Archive::open_stream("http://localhost/sample.zip", Format::Zip);
+        assert!(matches!(res, Err(ArkivError::UnsupportedArchive { .. })));
+        Ok(())
+    }
+
     #[tokio::test]
     #[cfg(feature = "zip")]
     async fn zip_archive() -> Result<()> {
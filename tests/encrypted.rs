@@ -0,0 +1,47 @@
+#[cfg(any(feature = "zip", feature = "sevenz"))]
+mod encrypted {
+    use arkiv::{Archive, Error as ArkivError};
+
+    type Error = Box<dyn std::error::Error>;
+    type Result<T> = std::result::Result<T, Error>;
+
+    #[test]
+    #[cfg(feature = "zip")]
+    fn zip_archive() -> Result<()> {
+        let mut archive = Archive::open("tests/sample/encrypted.zip")?;
+        assert!(matches!(
+            archive.entry_paths(),
+            Err(ArkivError::PasswordRequired)
+        ));
+        assert!(matches!(
+            archive.read_entry_to_string("sample/sample.txt"),
+            Err(ArkivError::PasswordRequired)
+        ));
+
+        let sandbox = tempfile::tempdir()?;
+        assert!(matches!(
+            archive.unpack(&sandbox),
+            Err(ArkivError::PasswordRequired)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "sevenz")]
+    fn sevenz_archive() -> Result<()> {
+        // only the contents are encrypted, the entries can still be listed
+        let mut archive = Archive::open("tests/sample/encrypted.7z")?;
+        assert_eq!(archive.entry_paths()?, vec!["sample/", "sample/sample.txt"]);
+        assert!(matches!(
+            archive.read_entry_to_string("sample/sample.txt"),
+            Err(ArkivError::PasswordRequired)
+        ));
+
+        let sandbox = tempfile::tempdir()?;
+        assert!(matches!(
+            archive.unpack(&sandbox),
+            Err(ArkivError::PasswordRequired)
+        ));
+        Ok(())
+    }
+}
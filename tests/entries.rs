@@ -7,16 +7,29 @@ type Result<T> = std::result::Result<T, Error>;
 #[allow(unused)]
 fn test(path: impl AsRef<Path>) -> Result<()> {
     let mut archive = Archive::open(path)?;
-    let mut actual = archive.entries()?;
+    let mut actual = archive.entry_paths()?;
     let mut expected = vec!["sample/", "sample/sample.txt"];
 
     actual.sort();
     expected.sort();
 
     /// call a second time to check that the rewind is done properly
-    assert!(archive.entries().is_ok());
+    assert!(archive.entry_paths().is_ok());
 
     assert_eq!(actual, expected);
+
+    let sorted = archive.entries_sorted()?;
+    let paths: Vec<&Path> = sorted.iter().map(|entry| entry.path()).collect();
+    assert_eq!(
+        paths,
+        vec![Path::new("sample/"), Path::new("sample/sample.txt")]
+    );
+    assert!(sorted[1].is_file());
+
+    // the deprecated name is still available
+    #[allow(deprecated)]
+    let deprecated = archive.entries()?;
+    assert_eq!(deprecated.len(), 2);
     Ok(())
 }
 
@@ -49,5 +62,37 @@ fn tar_xz_archive() -> Result<()> {
 #[cfg(all(feature = "zstd", feature = "tar"))]
 fn tar_zstd_archive() -> Result<()> {
     test("tests/sample/sample.tar.zstd")?;
-    test("tests/sample/sample.tar.zst")
+    test("tests/sample/sample.tar.zst")?;
+    // compressed as two concatenated zstd frames
+    test("tests/sample/sample-multiframe.tar.zst")
+}
+
+#[test]
+#[cfg(all(feature = "lzip", feature = "tar"))]
+fn tar_lz_archive() -> Result<()> {
+    test("tests/sample/sample.tar.lz")
+}
+
+#[test]
+#[cfg(feature = "tar")]
+fn gem_archive() -> Result<()> {
+    test("tests/sample/sample.gem")
+}
+
+#[test]
+#[cfg(feature = "crx")]
+fn crx_archive() -> Result<()> {
+    test("tests/sample/sample.crx")
+}
+
+#[test]
+#[cfg(feature = "sevenz")]
+fn sevenz_archive() -> Result<()> {
+    test("tests/sample/sample.7z")
+}
+
+#[test]
+#[cfg(all(feature = "rpm", feature = "gzip"))]
+fn rpm_archive() -> Result<()> {
+    test("tests/sample/sample.rpm")
 }
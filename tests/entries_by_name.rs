@@ -57,3 +57,56 @@ fn tar_zstd_archive() -> Result<()> {
     test("tests/sample/sample.tar.zstd")?;
     test("tests/sample/sample.tar.zst")
 }
+
+#[test]
+#[cfg(all(feature = "lzip", feature = "tar"))]
+fn tar_lz_archive() -> Result<()> {
+    test("tests/sample/sample.tar.lz")
+}
+
+#[test]
+#[cfg(feature = "tar")]
+fn gem_archive() -> Result<()> {
+    test("tests/sample/sample.gem")
+}
+
+#[test]
+#[cfg(feature = "crx")]
+fn crx_archive() -> Result<()> {
+    test("tests/sample/sample.crx")
+}
+
+#[test]
+#[cfg(feature = "sevenz")]
+fn sevenz_archive() -> Result<()> {
+    test("tests/sample/sample.7z")
+}
+
+#[test]
+#[cfg(all(feature = "rpm", feature = "gzip"))]
+fn rpm_archive() -> Result<()> {
+    test("tests/sample/sample.rpm")
+}
+
+#[test]
+#[cfg(feature = "zip")]
+fn case_insensitive() -> Result<()> {
+    let mut archive = Archive::open("tests/sample/mixed-case.zip")?;
+
+    let entry = archive.entry_by_name_ci("docs/setup.txt")?;
+    assert_eq!(entry.path(), Path::new("Docs/Setup.TXT"));
+
+    // the first of the entries only differing by their case is returned
+    let entry = archive.entry_by_name_ci("DOCS/Readme.md")?;
+    assert_eq!(entry.path(), Path::new("Docs/README.md"));
+
+    // the lookup by name stays case-sensitive
+    let entry = archive.entry_by_name("docs/readme.md")?;
+    assert_eq!(entry.path(), Path::new("docs/readme.md"));
+    let not_found = archive.entry_by_name("docs/setup.txt");
+    assert!(matches!(not_found, Err(arkiv::Error::FileNotFound)));
+
+    let not_found = archive.entry_by_name_ci("docs/install.txt");
+    assert!(matches!(not_found, Err(arkiv::Error::FileNotFound)));
+    Ok(())
+}
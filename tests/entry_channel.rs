@@ -0,0 +1,89 @@
+use arkiv::Archive;
+use std::path::Path;
+
+type Error = Box<dyn std::error::Error>;
+type Result<T> = std::result::Result<T, Error>;
+
+#[allow(unused)]
+fn test(path: impl AsRef<Path>) -> Result<()> {
+    let archive = Archive::open(path)?;
+    let mut expected = vec!["sample/", "sample/sample.txt"];
+    let mut actual: Vec<String> = vec![];
+
+    for item in archive.into_entry_channel(1) {
+        let (entry, data) = item?;
+        let path = entry.path().display().to_string();
+        if entry.is_file() {
+            assert_eq!(data, b"sample\n");
+        } else {
+            assert!(data.is_empty());
+        }
+        actual.push(path);
+    }
+
+    actual.sort();
+    expected.sort();
+
+    assert_eq!(actual, expected);
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "zip")]
+fn zip_archive() -> Result<()> {
+    test("tests/sample/sample.zip")
+}
+
+#[test]
+#[cfg(all(feature = "gzip", feature = "tar"))]
+fn tar_gz_archive() -> Result<()> {
+    test("tests/sample/sample.tar.gz")?;
+    test("tests/sample/sample.tgz")
+}
+
+#[test]
+#[cfg(all(feature = "bzip", feature = "tar"))]
+fn tar_bz2_archive() -> Result<()> {
+    test("tests/sample/sample.tar.bz2")
+}
+
+#[test]
+#[cfg(all(feature = "xz", feature = "tar"))]
+fn tar_xz_archive() -> Result<()> {
+    test("tests/sample/sample.tar.xz")
+}
+
+#[test]
+#[cfg(all(feature = "zstd", feature = "tar"))]
+fn tar_zstd_archive() -> Result<()> {
+    test("tests/sample/sample.tar.zstd")?;
+    test("tests/sample/sample.tar.zst")
+}
+
+#[test]
+#[cfg(feature = "tar")]
+fn gem_archive() -> Result<()> {
+    test("tests/sample/sample.gem")
+}
+
+#[test]
+#[cfg(feature = "crx")]
+fn crx_archive() -> Result<()> {
+    test("tests/sample/sample.crx")
+}
+
+#[test]
+#[cfg(feature = "sevenz")]
+fn sevenz_archive() -> Result<()> {
+    test("tests/sample/sample.7z")
+}
+
+#[test]
+#[cfg(feature = "zip")]
+fn dropped_receiver() -> Result<()> {
+    let archive = Archive::open("tests/sample/sample.zip")?;
+    let receiver = archive.into_entry_channel(0);
+    assert!(receiver.recv()?.is_ok());
+    drop(receiver);
+    Ok(())
+}
@@ -0,0 +1,100 @@
+use arkiv::Archive;
+use std::path::Path;
+
+type Error = Box<dyn std::error::Error>;
+type Result<T> = std::result::Result<T, Error>;
+
+#[allow(unused)]
+fn test(path: impl AsRef<Path>) -> Result<()> {
+    let mut archive = Archive::open(path)?;
+    assert_eq!(archive.entry_count()?, 2);
+    assert_eq!(archive.file_count()?, 1);
+    assert_eq!(archive.dir_count()?, 1);
+    assert!(!archive.is_empty()?);
+    assert_eq!(archive.total_uncompressed_size()?, 7);
+
+    // call a second time to check that the rewind is done properly
+    assert_eq!(archive.total_uncompressed_size()?, 7);
+    assert_eq!(archive.entry_count()?, 2);
+    Ok(())
+}
+
+#[test]
+#[cfg(any(feature = "zip", feature = "tar"))]
+fn empty_archive() -> Result<()> {
+    use arkiv::{ArchiveBuilder, Format};
+
+    let format = if cfg!(feature = "zip") {
+        Format::Zip
+    } else {
+        Format::Tar
+    };
+    let bytes = ArchiveBuilder::new(format.clone()).finish_to_bytes()?;
+    let mut archive = Archive::from_bytes(bytes, format)?;
+    assert!(archive.is_empty()?);
+    assert_eq!(archive.entry_count()?, 0);
+    Ok(())
+}
+#[test]
+#[cfg(feature = "zip")]
+fn zip_archive() -> Result<()> {
+    test("tests/sample/sample.zip")
+}
+
+#[test]
+#[cfg(all(feature = "gzip", feature = "tar"))]
+fn tar_gz_archive() -> Result<()> {
+    test("tests/sample/sample.tar.gz")?;
+    test("tests/sample/sample.tgz")
+}
+
+#[test]
+#[cfg(all(feature = "bzip", feature = "tar"))]
+fn tar_bz2_archive() -> Result<()> {
+    test("tests/sample/sample.tar.bz2")
+}
+
+#[test]
+#[cfg(all(feature = "xz", feature = "tar"))]
+fn tar_xz_archive() -> Result<()> {
+    test("tests/sample/sample.tar.xz")
+}
+
+#[test]
+#[cfg(all(feature = "zstd", feature = "tar"))]
+fn tar_zstd_archive() -> Result<()> {
+    test("tests/sample/sample.tar.zstd")?;
+    test("tests/sample/sample.tar.zst")?;
+    // compressed as two concatenated zstd frames
+    test("tests/sample/sample-multiframe.tar.zst")
+}
+
+#[test]
+#[cfg(all(feature = "lzip", feature = "tar"))]
+fn tar_lz_archive() -> Result<()> {
+    test("tests/sample/sample.tar.lz")
+}
+
+#[test]
+#[cfg(feature = "tar")]
+fn gem_archive() -> Result<()> {
+    test("tests/sample/sample.gem")
+}
+
+#[test]
+#[cfg(feature = "crx")]
+fn crx_archive() -> Result<()> {
+    test("tests/sample/sample.crx")
+}
+
+#[test]
+#[cfg(feature = "sevenz")]
+fn sevenz_archive() -> Result<()> {
+    test("tests/sample/sample.7z")
+}
+
+#[test]
+#[cfg(all(feature = "rpm", feature = "gzip"))]
+fn rpm_archive() -> Result<()> {
+    test("tests/sample/sample.rpm")
+}
@@ -0,0 +1,40 @@
+use arkiv::Archive;
+use std::path::Path;
+
+type Error = Box<dyn std::error::Error>;
+type Result<T> = std::result::Result<T, Error>;
+
+#[allow(unused)]
+fn test(path: impl AsRef<Path>) -> Result<()> {
+    let sandbox = tempfile::tempdir()?;
+    // a file where the directory of the entries should be created
+    std::fs::write(sandbox.path().join("sample"), "blocking")?;
+
+    let mut archive = Archive::open(path)?;
+    match archive.unpack(&sandbox) {
+        Err(err @ arkiv::Error::Entry { .. }) => {
+            assert!(err.to_string().starts_with("failed to extract 'sample/"));
+            assert!(std::error::Error::source(&err).is_some());
+        }
+        other => panic!("unexpected result: {other:?}"),
+    }
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "zip")]
+fn zip_archive() -> Result<()> {
+    test("tests/sample/sample.zip")
+}
+
+#[test]
+#[cfg(all(feature = "gzip", feature = "tar"))]
+fn tar_gz_archive() -> Result<()> {
+    test("tests/sample/sample.tar.gz")
+}
+
+#[test]
+#[cfg(all(feature = "rpm", feature = "gzip"))]
+fn rpm_archive() -> Result<()> {
+    test("tests/sample/sample.rpm")
+}
@@ -0,0 +1,105 @@
+use arkiv::Archive;
+use std::{io::Read, path::Path};
+
+type Error = Box<dyn std::error::Error>;
+type Result<T> = std::result::Result<T, Error>;
+
+/// reads the whole entry a few bytes at a time
+#[allow(unused)]
+fn read_in_chunks(mut reader: impl Read) -> Result<Vec<u8>> {
+    let mut contents = vec![];
+    let mut chunk = [0; 3];
+    loop {
+        let len = reader.read(&mut chunk)?;
+        if len == 0 {
+            break;
+        }
+        contents.extend_from_slice(&chunk[..len]);
+    }
+    Ok(contents)
+}
+
+#[allow(unused)]
+fn test(path: impl AsRef<Path>) -> Result<()> {
+    let mut archive = Archive::open(path)?;
+
+    let entry = archive.entry_by_name("sample/sample.txt")?;
+    let reader = archive.entry_reader(&entry)?;
+    assert_eq!(read_in_chunks(reader)?, b"sample\n");
+
+    let dir = archive.entry_by_name("sample/")?;
+    assert!(matches!(
+        archive.entry_reader(&dir),
+        Err(arkiv::Error::EntryTypeNotSupported(_))
+    ));
+
+    Ok(())
+}
+
+#[allow(unused)]
+fn test_large_entry(path: impl AsRef<Path>) -> Result<()> {
+    let mut archive = Archive::open(path)?;
+    let entry = archive.entry_by_name("large/data.bin")?;
+    let contents = read_in_chunks(archive.entry_reader(&entry)?)?;
+
+    // the entry contains the bytes 0, 1, ..., 250, 0, 1, ...
+    assert_eq!(contents.len(), 4 * 1024 * 1024);
+    assert!(contents
+        .iter()
+        .enumerate()
+        .all(|(i, &b)| b == (i % 251) as u8));
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "zip")]
+fn zip_archive() -> Result<()> {
+    test("tests/sample/sample.zip")?;
+    test_large_entry("tests/sample/large-entry.zip")
+}
+
+#[test]
+#[cfg(all(feature = "gzip", feature = "tar"))]
+fn tar_gz_archive() -> Result<()> {
+    test("tests/sample/sample.tar.gz")?;
+    test("tests/sample/sample.tgz")?;
+    test_large_entry("tests/sample/large-entry.tar.gz")
+}
+
+#[test]
+#[cfg(all(feature = "bzip", feature = "tar"))]
+fn tar_bz2_archive() -> Result<()> {
+    test("tests/sample/sample.tar.bz2")
+}
+
+#[test]
+#[cfg(all(feature = "xz", feature = "tar"))]
+fn tar_xz_archive() -> Result<()> {
+    test("tests/sample/sample.tar.xz")
+}
+
+#[test]
+#[cfg(all(feature = "zstd", feature = "tar"))]
+fn tar_zstd_archive() -> Result<()> {
+    test("tests/sample/sample.tar.zstd")?;
+    test("tests/sample/sample.tar.zst")
+}
+
+#[test]
+#[cfg(feature = "tar")]
+fn gem_archive() -> Result<()> {
+    test("tests/sample/sample.gem")
+}
+
+#[test]
+#[cfg(feature = "crx")]
+fn crx_archive() -> Result<()> {
+    test("tests/sample/sample.crx")
+}
+
+#[test]
+#[cfg(feature = "sevenz")]
+fn sevenz_archive() -> Result<()> {
+    test("tests/sample/sample.7z")
+}
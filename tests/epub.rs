@@ -0,0 +1,32 @@
+#[cfg(feature = "zip")]
+mod epub {
+    use arkiv::{Archive, Format};
+
+    type Error = Box<dyn std::error::Error>;
+    type Result<T> = std::result::Result<T, Error>;
+
+    #[test]
+    fn epub_archive() -> Result<()> {
+        let mut archive = Archive::open("tests/sample/sample.epub")?;
+        assert_eq!(archive.format(), &Format::Zip);
+        assert_eq!(
+            archive.entry_paths()?,
+            vec![
+                "mimetype",
+                "META-INF/container.xml",
+                "OEBPS/content.opf",
+                "OEBPS/sample.xhtml",
+                "OEBPS/style.css",
+            ]
+        );
+        assert_eq!(
+            archive.read_entry_to_string("mimetype")?,
+            "application/epub+zip"
+        );
+        assert_eq!(
+            archive.read_entry_to_string("OEBPS/style.css")?,
+            "p { color: black; }\n"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,52 @@
+#[cfg(all(unix, feature = "zip"))]
+mod executable_extensions {
+    use arkiv::{Archive, ArchiveOptions};
+    use std::{fs::metadata, os::unix::fs::PermissionsExt, path::Path};
+
+    type Error = Box<dyn std::error::Error>;
+    type Result<T> = std::result::Result<T, Error>;
+
+    fn is_executable(path: impl AsRef<Path>) -> Result<bool> {
+        Ok(metadata(path)?.permissions().mode() & 0o111 != 0)
+    }
+
+    #[test]
+    fn unpack() -> Result<()> {
+        let sandbox = tempfile::tempdir()?;
+        let options = ArchiveOptions {
+            executable_extensions: vec!["sh".to_string()],
+            ..Default::default()
+        };
+        let mut archive = Archive::open("tests/sample/scripts.zip")?.with_options(options);
+        archive.unpack(&sandbox)?;
+
+        assert!(is_executable(sandbox.path().join("scripts/run.sh"))?);
+        assert!(!is_executable(sandbox.path().join("scripts/README.txt"))?);
+        Ok(())
+    }
+
+    #[test]
+    fn unpack_entry() -> Result<()> {
+        let sandbox = tempfile::tempdir()?;
+        let options = ArchiveOptions {
+            executable_extensions: vec![".SH".to_string()],
+            ..Default::default()
+        };
+        let mut archive = Archive::open("tests/sample/scripts.zip")?.with_options(options);
+        let run_sh = archive.entry_by_name("scripts/run.sh")?;
+        archive.unpack_entry(&run_sh, &sandbox)?;
+
+        assert!(is_executable(sandbox.path().join("scripts/run.sh"))?);
+        Ok(())
+    }
+
+    #[test]
+    fn default_options() -> Result<()> {
+        let sandbox = tempfile::tempdir()?;
+        let mut archive = Archive::open("tests/sample/scripts.zip")?;
+        archive.unpack(&sandbox)?;
+
+        assert!(!is_executable(sandbox.path().join("scripts/run.sh"))?);
+        Ok(())
+    }
+}
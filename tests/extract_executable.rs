@@ -0,0 +1,81 @@
+use arkiv::Archive;
+use std::{fs::read_to_string, path::Path};
+
+type Error = Box<dyn std::error::Error>;
+type Result<T> = std::result::Result<T, Error>;
+
+#[allow(unused)]
+fn test(path: impl AsRef<Path>) -> Result<()> {
+    let sandbox = tempfile::tempdir()?;
+    let mut archive = Archive::open(path)?;
+
+    let dest_file = sandbox.path().join("bin/tool");
+    let tool = archive.extract_executable("sample/sample.txt", &dest_file)?;
+    assert_eq!(tool, dest_file);
+    assert_eq!(read_to_string(&tool)?, "sample\n");
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = std::fs::metadata(&tool)?.permissions().mode();
+        assert_eq!(mode & 0o777, 0o755);
+    }
+
+    let not_found = archive.extract_executable("sample/not_found", &dest_file);
+    assert!(matches!(not_found, Err(arkiv::Error::FileNotFound)));
+
+    let dir = archive.extract_executable("sample/", &dest_file);
+    assert!(matches!(dir, Err(arkiv::Error::EntryTypeNotSupported(_))));
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "zip")]
+fn zip_archive() -> Result<()> {
+    test("tests/sample/sample.zip")
+}
+
+#[test]
+#[cfg(all(feature = "gzip", feature = "tar"))]
+fn tar_gz_archive() -> Result<()> {
+    test("tests/sample/sample.tar.gz")?;
+    test("tests/sample/sample.tgz")
+}
+
+#[test]
+#[cfg(all(feature = "bzip", feature = "tar"))]
+fn tar_bz2_archive() -> Result<()> {
+    test("tests/sample/sample.tar.bz2")
+}
+
+#[test]
+#[cfg(all(feature = "xz", feature = "tar"))]
+fn tar_xz_archive() -> Result<()> {
+    test("tests/sample/sample.tar.xz")
+}
+
+#[test]
+#[cfg(all(feature = "zstd", feature = "tar"))]
+fn tar_zstd_archive() -> Result<()> {
+    test("tests/sample/sample.tar.zstd")?;
+    test("tests/sample/sample.tar.zst")
+}
+
+#[test]
+#[cfg(feature = "tar")]
+fn gem_archive() -> Result<()> {
+    test("tests/sample/sample.gem")
+}
+
+#[test]
+#[cfg(feature = "crx")]
+fn crx_archive() -> Result<()> {
+    test("tests/sample/sample.crx")
+}
+
+#[test]
+#[cfg(feature = "sevenz")]
+fn sevenz_archive() -> Result<()> {
+    test("tests/sample/sample.7z")
+}
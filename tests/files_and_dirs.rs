@@ -0,0 +1,85 @@
+use arkiv::{Archive, Entry};
+use std::path::Path;
+
+type Error = Box<dyn std::error::Error>;
+type Result<T> = std::result::Result<T, Error>;
+
+#[allow(unused)]
+fn test(path: impl AsRef<Path>) -> Result<()> {
+    let mut archive = Archive::open(path)?;
+
+    let files = archive.files()?.collect::<arkiv::Result<Vec<Entry>>>()?;
+    assert_eq!(files.len(), 1);
+    assert!(files.iter().all(Entry::is_file));
+    assert_eq!(files[0].path(), Path::new("sample/sample.txt"));
+
+    let dirs = archive.dirs()?.collect::<arkiv::Result<Vec<Entry>>>()?;
+    assert_eq!(dirs.len(), 1);
+    assert!(dirs.iter().all(Entry::is_dir));
+    assert_eq!(dirs[0].path(), Path::new("sample/"));
+
+    Ok(())
+}
+#[test]
+#[cfg(feature = "zip")]
+fn zip_archive() -> Result<()> {
+    test("tests/sample/sample.zip")
+}
+
+#[test]
+#[cfg(all(feature = "gzip", feature = "tar"))]
+fn tar_gz_archive() -> Result<()> {
+    test("tests/sample/sample.tar.gz")?;
+    test("tests/sample/sample.tgz")
+}
+
+#[test]
+#[cfg(all(feature = "bzip", feature = "tar"))]
+fn tar_bz2_archive() -> Result<()> {
+    test("tests/sample/sample.tar.bz2")
+}
+
+#[test]
+#[cfg(all(feature = "xz", feature = "tar"))]
+fn tar_xz_archive() -> Result<()> {
+    test("tests/sample/sample.tar.xz")
+}
+
+#[test]
+#[cfg(all(feature = "zstd", feature = "tar"))]
+fn tar_zstd_archive() -> Result<()> {
+    test("tests/sample/sample.tar.zstd")?;
+    test("tests/sample/sample.tar.zst")?;
+    // compressed as two concatenated zstd frames
+    test("tests/sample/sample-multiframe.tar.zst")
+}
+
+#[test]
+#[cfg(all(feature = "lzip", feature = "tar"))]
+fn tar_lz_archive() -> Result<()> {
+    test("tests/sample/sample.tar.lz")
+}
+
+#[test]
+#[cfg(feature = "tar")]
+fn gem_archive() -> Result<()> {
+    test("tests/sample/sample.gem")
+}
+
+#[test]
+#[cfg(feature = "crx")]
+fn crx_archive() -> Result<()> {
+    test("tests/sample/sample.crx")
+}
+
+#[test]
+#[cfg(feature = "sevenz")]
+fn sevenz_archive() -> Result<()> {
+    test("tests/sample/sample.7z")
+}
+
+#[test]
+#[cfg(all(feature = "rpm", feature = "gzip"))]
+fn rpm_archive() -> Result<()> {
+    test("tests/sample/sample.rpm")
+}
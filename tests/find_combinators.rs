@@ -0,0 +1,102 @@
+use arkiv::{Archive, Entry};
+use std::path::Path;
+
+type Error = Box<dyn std::error::Error>;
+type Result<T> = std::result::Result<T, Error>;
+
+#[allow(unused)]
+fn paths(entries: impl Iterator<Item = arkiv::Result<Entry>>) -> Result<Vec<String>> {
+    let mut paths = vec![];
+    for entry in entries {
+        paths.push(entry?.path().display().to_string());
+    }
+    paths.sort();
+    Ok(paths)
+}
+
+#[allow(unused)]
+fn test(path: impl AsRef<Path>) -> Result<()> {
+    let mut archive = Archive::open(path)?;
+    let is_small = |entry: &Entry| entry.size() < 100;
+    let is_large = |entry: &Entry| entry.size() >= 100;
+
+    let found = archive.find(Entry::is_file)?.and(is_small);
+    assert_eq!(paths(found)?, vec!["sample/sample.txt"]);
+
+    let found = archive.find(Entry::is_file)?.and(is_large);
+    assert!(paths(found)?.is_empty());
+
+    let found = archive
+        .find(Entry::is_file)?
+        .and(is_large)
+        .or(Entry::is_dir);
+    assert_eq!(paths(found)?, vec!["sample/"]);
+
+    let found = archive.files()?.or(Entry::is_dir).and(is_small);
+    assert_eq!(paths(found)?, vec!["sample/", "sample/sample.txt"]);
+
+    Ok(())
+}
+#[test]
+#[cfg(feature = "zip")]
+fn zip_archive() -> Result<()> {
+    test("tests/sample/sample.zip")
+}
+
+#[test]
+#[cfg(all(feature = "gzip", feature = "tar"))]
+fn tar_gz_archive() -> Result<()> {
+    test("tests/sample/sample.tar.gz")?;
+    test("tests/sample/sample.tgz")
+}
+
+#[test]
+#[cfg(all(feature = "bzip", feature = "tar"))]
+fn tar_bz2_archive() -> Result<()> {
+    test("tests/sample/sample.tar.bz2")
+}
+
+#[test]
+#[cfg(all(feature = "xz", feature = "tar"))]
+fn tar_xz_archive() -> Result<()> {
+    test("tests/sample/sample.tar.xz")
+}
+
+#[test]
+#[cfg(all(feature = "zstd", feature = "tar"))]
+fn tar_zstd_archive() -> Result<()> {
+    test("tests/sample/sample.tar.zstd")?;
+    test("tests/sample/sample.tar.zst")?;
+    // compressed as two concatenated zstd frames
+    test("tests/sample/sample-multiframe.tar.zst")
+}
+
+#[test]
+#[cfg(all(feature = "lzip", feature = "tar"))]
+fn tar_lz_archive() -> Result<()> {
+    test("tests/sample/sample.tar.lz")
+}
+
+#[test]
+#[cfg(feature = "tar")]
+fn gem_archive() -> Result<()> {
+    test("tests/sample/sample.gem")
+}
+
+#[test]
+#[cfg(feature = "crx")]
+fn crx_archive() -> Result<()> {
+    test("tests/sample/sample.crx")
+}
+
+#[test]
+#[cfg(feature = "sevenz")]
+fn sevenz_archive() -> Result<()> {
+    test("tests/sample/sample.7z")
+}
+
+#[test]
+#[cfg(all(feature = "rpm", feature = "gzip"))]
+fn rpm_archive() -> Result<()> {
+    test("tests/sample/sample.rpm")
+}
@@ -0,0 +1,74 @@
+#[cfg(feature = "glob")]
+mod find_glob {
+    use arkiv::Archive;
+    use std::path::Path;
+
+    type Error = Box<dyn std::error::Error>;
+    type Result<T> = std::result::Result<T, Error>;
+
+    fn matching(archive: &mut Archive, pattern: &str) -> Result<Vec<String>> {
+        let mut paths = vec![];
+        for entry in archive.find_glob(pattern)? {
+            paths.push(entry?.path().display().to_string());
+        }
+        Ok(paths)
+    }
+
+    #[allow(unused)]
+    fn test(path: impl AsRef<Path>) -> Result<()> {
+        let mut archive = Archive::open(path)?;
+        assert_eq!(
+            matching(&mut archive, "**/*.txt")?,
+            vec!["sample/sample.txt"]
+        );
+        assert_eq!(
+            matching(&mut archive, "sample/*")?,
+            vec!["sample/sample.txt"]
+        );
+        assert_eq!(
+            matching(&mut archive, "sample/?ample.*")?,
+            vec!["sample/sample.txt"]
+        );
+        assert_eq!(matching(&mut archive, "*")?, vec!["sample/"]);
+        assert_eq!(matching(&mut archive, "**")?.len(), 2);
+        assert!(matching(&mut archive, "*.txt")?.is_empty());
+        assert!(matching(&mut archive, "**/*.rs")?.is_empty());
+        Ok(())
+    }
+
+    #[allow(unused)]
+    fn test_nested(path: impl AsRef<Path>) -> Result<()> {
+        let mut archive = Archive::open(path)?;
+        assert_eq!(matching(&mut archive, "many/000?.txt")?.len(), 10);
+        assert_eq!(matching(&mut archive, "**/duplicate.txt")?.len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(all(feature = "gzip", feature = "tar"))]
+    fn invalid_pattern() -> Result<()> {
+        let mut archive = Archive::open("tests/sample/sample.tar.gz")?;
+        let res = archive.find_glob("***").map(|_| ());
+        assert!(matches!(res, Err(arkiv::Error::InvalidPattern(_))));
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "zip")]
+    fn zip_archive() -> Result<()> {
+        test("tests/sample/sample.zip")
+    }
+
+    #[test]
+    #[cfg(all(feature = "gzip", feature = "tar"))]
+    fn tar_gz_archive() -> Result<()> {
+        test("tests/sample/sample.tar.gz")?;
+        test_nested("tests/sample/many-files.tar.gz")
+    }
+
+    #[test]
+    #[cfg(feature = "sevenz")]
+    fn sevenz_archive() -> Result<()> {
+        test("tests/sample/sample.7z")
+    }
+}
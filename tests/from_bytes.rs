@@ -0,0 +1,85 @@
+use arkiv::{Archive, Format};
+use std::{
+    fs::{read, read_to_string},
+    path::Path,
+};
+
+type Error = Box<dyn std::error::Error>;
+type Result<T> = std::result::Result<T, Error>;
+
+#[allow(unused)]
+fn test(path: impl AsRef<Path>, format: Format) -> Result<()> {
+    let sandbox = tempfile::tempdir()?;
+    let mut archive = Archive::from_bytes(read(path)?, format.clone())?;
+    assert_eq!(archive.format(), &format);
+
+    let mut actual = archive.entry_paths()?;
+    let mut expected = vec!["sample/", "sample/sample.txt"];
+    actual.sort();
+    expected.sort();
+    assert_eq!(actual, expected);
+
+    archive.unpack(&sandbox)?;
+    assert_eq!(
+        read_to_string(sandbox.path().join("sample/sample.txt"))?,
+        "sample\n"
+    );
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "zip")]
+fn zip_archive() -> Result<()> {
+    test("tests/sample/sample.zip", Format::Zip)
+}
+
+#[test]
+#[cfg(all(feature = "gzip", feature = "tar"))]
+fn tar_gz_archive() -> Result<()> {
+    test("tests/sample/sample.tar.gz", Format::TarGzip)?;
+    test("tests/sample/sample.tgz", Format::TarGzip)
+}
+
+#[test]
+#[cfg(all(feature = "bzip", feature = "tar"))]
+fn tar_bz2_archive() -> Result<()> {
+    test("tests/sample/sample.tar.bz2", Format::TarBzip2)
+}
+
+#[test]
+#[cfg(all(feature = "xz", feature = "tar"))]
+fn tar_xz_archive() -> Result<()> {
+    test("tests/sample/sample.tar.xz", Format::TarXz2)
+}
+
+#[test]
+#[cfg(all(feature = "zstd", feature = "tar"))]
+fn tar_zst_archive() -> Result<()> {
+    test("tests/sample/sample.tar.zstd", Format::TarZstd)?;
+    test("tests/sample/sample.tar.zst", Format::TarZstd)
+}
+
+#[test]
+#[cfg(feature = "tar")]
+fn gem_archive() -> Result<()> {
+    test("tests/sample/sample.gem", Format::Tar)
+}
+
+#[test]
+#[cfg(feature = "crx")]
+fn crx_archive() -> Result<()> {
+    test("tests/sample/sample.crx", Format::Crx)
+}
+
+#[test]
+#[cfg(feature = "sevenz")]
+fn sevenz_archive() -> Result<()> {
+    test("tests/sample/sample.7z", Format::SevenZ)
+}
+
+#[test]
+fn unsupported_format() -> Result<()> {
+    let res = Archive::from_bytes(vec![], Format::Unknown);
+    assert!(matches!(res, Err(arkiv::Error::UnsupportedArchive { .. })));
+    Ok(())
+}
@@ -0,0 +1,46 @@
+#[cfg(feature = "zip")]
+mod index_progress {
+    use arkiv::Archive;
+    use std::{cell::RefCell, path::Path, rc::Rc};
+
+    type Error = Box<dyn std::error::Error>;
+    type Result<T> = std::result::Result<T, Error>;
+
+    #[allow(unused)]
+    fn test(path: impl AsRef<Path>, indexed: bool) -> Result<()> {
+        let progress = Rc::new(RefCell::new(vec![]));
+        let reported = progress.clone();
+        let mut archive = Archive::open_with_index_progress(path, move |read, total| {
+            reported.borrow_mut().push((read, total));
+        })?;
+        assert_eq!(archive.entry_paths()?.len(), 2);
+
+        let progress = progress.borrow();
+        if indexed {
+            let (read, total) = *progress.last().unwrap();
+            assert!(total > 0);
+            assert_eq!(read, total);
+            assert!(progress.windows(2).all(|pair| pair[0].0 < pair[1].0));
+        } else {
+            assert!(progress.is_empty());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn zip_archive() -> Result<()> {
+        test("tests/sample/sample.zip", true)
+    }
+
+    #[test]
+    #[cfg(feature = "crx")]
+    fn crx_archive() -> Result<()> {
+        test("tests/sample/sample.crx", true)
+    }
+
+    #[test]
+    #[cfg(all(feature = "gzip", feature = "tar"))]
+    fn tar_gz_archive() -> Result<()> {
+        test("tests/sample/sample.tar.gz", false)
+    }
+}
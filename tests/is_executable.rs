@@ -0,0 +1,38 @@
+use arkiv::Archive;
+use std::path::Path;
+
+type Error = Box<dyn std::error::Error>;
+type Result<T> = std::result::Result<T, Error>;
+
+#[allow(unused)]
+fn test(path: impl AsRef<Path>) -> Result<()> {
+    let mut archive = Archive::open(path)?;
+    assert!(archive.entry_by_name("bin/tool")?.is_executable());
+    assert!(!archive.entry_by_name("bin/README")?.is_executable());
+    let dir = archive
+        .find(|entry| entry.is_dir())?
+        .next()
+        .expect("bin/")?;
+    assert!(!dir.is_executable());
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "zip")]
+fn zip_archive() -> Result<()> {
+    test("tests/sample/executable.zip")
+}
+
+#[test]
+#[cfg(feature = "zip")]
+fn zip_archive_without_unix_mode() -> Result<()> {
+    let mut archive = Archive::open("tests/sample/scripts.zip")?;
+    assert!(!archive.entry_by_name("scripts/run.sh")?.is_executable());
+    Ok(())
+}
+
+#[test]
+#[cfg(all(feature = "gzip", feature = "tar"))]
+fn tar_gz_archive() -> Result<()> {
+    test("tests/sample/executable.tar.gz")
+}
@@ -0,0 +1,25 @@
+use arkiv::Archive;
+use std::path::Path;
+
+type Error = Box<dyn std::error::Error>;
+type Result<T> = std::result::Result<T, Error>;
+
+/// size of the entry stored in the fixture, which only contains the headers
+const LARGE_SIZE: u64 = 9 * 1024 * 1024 * 1024;
+
+#[allow(unused)]
+fn test(path: impl AsRef<Path>) -> Result<()> {
+    let mut archive = Archive::open(path)?;
+    let entry = archive.entries_iter()?.next().expect("one entry")?;
+    assert_eq!(entry.path(), Path::new("large/large.bin"));
+    assert!(entry.is_file());
+    assert_eq!(entry.size(), LARGE_SIZE);
+    Ok(())
+}
+
+#[test]
+#[cfg(all(feature = "gzip", feature = "tar"))]
+fn tar_gz_archive() -> Result<()> {
+    // the ustar size field is zero, the actual size is in a PAX extended header
+    test("tests/sample/large.tar.gz")
+}
@@ -0,0 +1,98 @@
+use arkiv::Archive;
+use std::{
+    path::Path,
+    time::{Duration, SystemTime},
+};
+
+type Error = Box<dyn std::error::Error>;
+type Result<T> = std::result::Result<T, Error>;
+
+/// 2023-09-12 16:02:36 UTC, when the tar based samples were created
+#[allow(unused)]
+const TAR_MTIME: u64 = 1694534556;
+
+/// 2023-09-15 12:18:42, when the zip based samples were created
+#[allow(unused)]
+const ZIP_MTIME: u64 = 1694780322;
+
+#[allow(unused)]
+fn test(path: impl AsRef<Path>, expected: Option<u64>) -> Result<()> {
+    let expected = expected.map(|secs| SystemTime::UNIX_EPOCH + Duration::from_secs(secs));
+    let mut archive = Archive::open(path)?;
+    for entry in archive.entries_iter()? {
+        assert_eq!(entry?.last_modified(), expected);
+    }
+
+    // the modification time of the unpacked files is restored
+    if let Some(expected) = expected {
+        let file = archive.find(|entry| entry.is_file())?.next().unwrap()?;
+
+        let sandbox = tempfile::tempdir()?;
+        archive.unpack(&sandbox)?;
+        let outpath = sandbox.path().join(file.path());
+        assert_eq!(outpath.metadata()?.modified()?, expected);
+
+        let sandbox = tempfile::tempdir()?;
+        archive.unpack_entry(&file, &sandbox)?;
+        let outpath = sandbox.path().join(file.path());
+        assert_eq!(outpath.metadata()?.modified()?, expected);
+    }
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "zip")]
+fn zip_archive() -> Result<()> {
+    test("tests/sample/sample.zip", Some(ZIP_MTIME))?;
+    test("tests/sample/zeroed-timestamp.zip", None)
+}
+
+#[test]
+#[cfg(all(feature = "gzip", feature = "tar"))]
+fn tar_gz_archive() -> Result<()> {
+    test("tests/sample/sample.tar.gz", Some(TAR_MTIME))?;
+    test("tests/sample/sample.tgz", Some(TAR_MTIME))
+}
+
+#[test]
+#[cfg(all(feature = "bzip", feature = "tar"))]
+fn tar_bz2_archive() -> Result<()> {
+    test("tests/sample/sample.tar.bz2", Some(TAR_MTIME))
+}
+
+#[test]
+#[cfg(all(feature = "xz", feature = "tar"))]
+fn tar_xz_archive() -> Result<()> {
+    test("tests/sample/sample.tar.xz", Some(TAR_MTIME))
+}
+
+#[test]
+#[cfg(all(feature = "zstd", feature = "tar"))]
+fn tar_zstd_archive() -> Result<()> {
+    test("tests/sample/sample.tar.zstd", Some(TAR_MTIME))?;
+    test("tests/sample/sample.tar.zst", Some(TAR_MTIME))
+}
+
+#[test]
+#[cfg(feature = "tar")]
+fn gem_archive() -> Result<()> {
+    test("tests/sample/sample.gem", Some(TAR_MTIME))
+}
+
+#[test]
+#[cfg(feature = "crx")]
+fn crx_archive() -> Result<()> {
+    test("tests/sample/sample.crx", Some(ZIP_MTIME))
+}
+
+#[test]
+#[cfg(feature = "deb")]
+fn deb_archive() -> Result<()> {
+    test("tests/sample/sample.deb", Some(TAR_MTIME))
+}
+
+#[test]
+#[cfg(feature = "sevenz")]
+fn sevenz_archive() -> Result<()> {
+    test("tests/sample/sample.7z", None)
+}
@@ -0,0 +1,35 @@
+use arkiv::Archive;
+use std::path::Path;
+
+type Error = Box<dyn std::error::Error>;
+type Result<T> = std::result::Result<T, Error>;
+
+#[allow(unused)]
+fn test(path: impl AsRef<Path>) -> Result<()> {
+    let mut strict = Archive::open(&path)?;
+    assert!(strict.entry_paths().is_err());
+
+    let mut lenient = Archive::open(&path)?.lenient(true);
+    let mut actual = lenient.entry_paths()?;
+    let mut expected = vec!["sample/", "sample/sample.txt"];
+
+    actual.sort();
+    expected.sort();
+
+    assert_eq!(actual, expected);
+    Ok(())
+}
+
+#[test]
+#[cfg(all(feature = "gzip", feature = "tar"))]
+fn tar_gz_archive_with_bom() -> Result<()> {
+    test("tests/sample/sample-bom.tar.gz")
+}
+
+#[test]
+#[cfg(all(feature = "gzip", feature = "tar"))]
+fn tar_gz_archive_without_bom() -> Result<()> {
+    let mut lenient = Archive::open("tests/sample/sample.tar.gz")?.lenient(true);
+    assert_eq!(lenient.entry_paths()?.len(), 2);
+    Ok(())
+}
@@ -0,0 +1,66 @@
+#[cfg(all(feature = "gzip", feature = "tar"))]
+mod many_files {
+    use arkiv::{Archive, Entry};
+    use std::{fs::read_to_string, io::Read};
+
+    type Error = Box<dyn std::error::Error>;
+    type Result<T> = std::result::Result<T, Error>;
+
+    const PATH: &str = "tests/sample/many-files.tar.gz";
+
+    fn files(archive: &mut Archive) -> Result<Vec<Entry>> {
+        let mut files = vec![];
+        for entry in archive.entries_iter()? {
+            let entry = entry?;
+            if entry.is_file() {
+                files.push(entry);
+            }
+        }
+        Ok(files)
+    }
+
+    #[test]
+    fn unpack_entry() -> Result<()> {
+        let sandbox = tempfile::tempdir()?;
+        let mut archive = Archive::open(PATH)?;
+        let files = files(&mut archive)?;
+        assert_eq!(files.len(), 1002);
+
+        for (n, entry) in files.iter().take(1000).enumerate() {
+            archive.unpack_entry(entry, &sandbox)?;
+            let path = sandbox.path().join(format!("many/{n:04}.txt"));
+            assert_eq!(read_to_string(path)?, format!("{n}\n"));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn duplicate_entries() -> Result<()> {
+        let mut archive = Archive::open(PATH)?;
+        let files = files(&mut archive)?;
+
+        // entries sharing the same path are looked up by their index
+        let mut contents = vec![];
+        for entry in &files[1000..] {
+            let mut content = String::new();
+            archive.entry_reader(entry)?.read_to_string(&mut content)?;
+            contents.push(content);
+        }
+        assert_eq!(contents, vec!["first\n", "second\n"]);
+
+        let sandbox = tempfile::tempdir()?;
+        archive.unpack_entry(&files[1000], &sandbox)?;
+        assert_eq!(
+            read_to_string(sandbox.path().join("many/duplicate.txt"))?,
+            "first\n"
+        );
+
+        // and can be looked up directly by their index
+        let last = archive.entry_by_index(files[1001].index())?;
+        assert_eq!(last.path(), files[1001].path());
+        let mut content = String::new();
+        archive.entry_reader(&last)?.read_to_string(&mut content)?;
+        assert_eq!(content, "second\n");
+        Ok(())
+    }
+}
@@ -0,0 +1,194 @@
+use arkiv::{Archive, Error as ArkivError};
+
+type Error = Box<dyn std::error::Error>;
+type Result<T> = std::result::Result<T, Error>;
+
+#[test]
+fn archive_not_found() -> Result<()> {
+    let res = Archive::open("tests/sample/not_found.tar.gz");
+    assert!(matches!(res, Err(ArkivError::ArchiveNotFound(_))));
+    Ok(())
+}
+
+#[test]
+fn unrecognized_format() -> Result<()> {
+    let res = Archive::open("README.md");
+    assert!(matches!(res, Err(ArkivError::UnrecognizedFormat(_))));
+    Ok(())
+}
+
+#[test]
+fn squashfs_image() -> Result<()> {
+    let sandbox = tempfile::tempdir()?;
+    let path = sandbox.path().join("sample.snap");
+    std::fs::write(&path, b"hsqs")?;
+
+    match Archive::open(&path) {
+        Err(ArkivError::UnsupportedArchive { path: p, message }) => {
+            assert_eq!(p, Some(path));
+            assert!(message.contains("squashfs"));
+        }
+        other => panic!("unexpected result: {:?}", other.map(|_| ())),
+    }
+    Ok(())
+}
+
+#[test]
+fn iso_image() -> Result<()> {
+    let sandbox = tempfile::tempdir()?;
+    let path = sandbox.path().join("image");
+    let mut image = vec![0; 32774];
+    image[32769..].copy_from_slice(b"CD001");
+    std::fs::write(&path, image)?;
+
+    match Archive::open_with_magic_detection(&path) {
+        Err(ArkivError::UnsupportedArchive { path: p, message }) => {
+            assert_eq!(p, Some(path));
+            assert!(message.contains("iso9660"));
+        }
+        other => panic!("unexpected result: {:?}", other.map(|_| ())),
+    }
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "zip")]
+fn zip_archive() -> Result<()> {
+    assert!(Archive::open("tests/sample/sample.zip").is_ok());
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "zip")]
+fn invalid_zip_archive() -> Result<()> {
+    let sandbox = tempfile::tempdir()?;
+    let path = sandbox.path().join("invalid.zip");
+    std::fs::write(&path, b"not a zip archive")?;
+
+    let res = Archive::open(&path).and_then(|mut archive| archive.entry_paths());
+    match res {
+        Err(err @ ArkivError::InvalidArchive { .. }) => {
+            assert!(err.to_string().contains(&*path.to_string_lossy()));
+            assert!(matches!(err, ArkivError::InvalidArchive { path: Some(p), .. } if p == path));
+        }
+        other => panic!("unexpected result: {other:?}"),
+    }
+    Ok(())
+}
+
+#[test]
+#[cfg(not(feature = "zip"))]
+fn unsupported_zip_archive() -> Result<()> {
+    let res = Archive::open("tests/sample/sample.zip");
+    assert!(matches!(res, Err(ArkivError::UnsupportedArchive { .. })));
+    Ok(())
+}
+
+#[test]
+#[cfg(all(feature = "tar", feature = "lzip", not(feature = "xz")))]
+fn unsupported_tar_xz_archive_with_lzip() -> Result<()> {
+    // lzip is decoded with the xz library, which must not enable xz archives
+    let res = Archive::open("tests/sample/sample.tar.xz");
+    assert!(matches!(res, Err(ArkivError::UnsupportedArchive { .. })));
+    Ok(())
+}
+
+#[test]
+#[cfg(all(feature = "gzip", feature = "tar"))]
+fn open_with_format() -> Result<()> {
+    let sandbox = tempfile::tempdir()?;
+    let path = sandbox.path().join("archive");
+    std::fs::copy("tests/sample/sample.tar.gz", &path)?;
+
+    let mut archive = Archive::open_with_format(&path, arkiv::Format::TarGzip)?;
+    assert_eq!(archive.format(), &arkiv::Format::TarGzip);
+    assert_eq!(archive.format_display(), "TAR+GZip");
+    assert_eq!(archive.entry_paths()?.len(), 2);
+    Ok(())
+}
+
+#[test]
+fn open_with_format_not_found() -> Result<()> {
+    let res = Archive::open_with_format("tests/sample/not_found", arkiv::Format::Tar);
+    assert!(matches!(res, Err(ArkivError::ArchiveNotFound(_))));
+    Ok(())
+}
+
+#[test]
+fn open_with_unknown_format() -> Result<()> {
+    let res = Archive::open_with_format("README.md", arkiv::Format::Unknown);
+    assert!(matches!(res, Err(ArkivError::UnsupportedArchive { .. })));
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "zip")]
+fn open_with_magic_detection() -> Result<()> {
+    let sandbox = tempfile::tempdir()?;
+    let path = sandbox.path().join("archive");
+    std::fs::copy("tests/sample/sample.zip", &path)?;
+
+    assert!(matches!(
+        Archive::open(&path),
+        Err(ArkivError::UnrecognizedFormat(_))
+    ));
+    let mut archive = Archive::open_with_magic_detection(&path)?;
+    assert_eq!(archive.format(), &arkiv::Format::Zip);
+    assert_eq!(archive.entry_paths()?, vec!["sample/", "sample/sample.txt"]);
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "tar")]
+fn open_with_magic_detection_tar() -> Result<()> {
+    let sandbox = tempfile::tempdir()?;
+    let path = sandbox.path().join("archive");
+    std::fs::copy("tests/sample/special.tar", &path)?;
+
+    let archive = Archive::open_with_magic_detection(&path)?;
+    assert_eq!(archive.format(), &arkiv::Format::Tar);
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "gzip")]
+fn open_with_magic_detection_gzip() -> Result<()> {
+    let sandbox = tempfile::tempdir()?;
+    let path = sandbox.path().join("sample");
+    std::fs::copy("tests/sample/sample.txt.gz", &path)?;
+
+    let archive = Archive::open_with_magic_detection(&path)?;
+    assert_eq!(archive.format(), &arkiv::Format::Gzip);
+    Ok(())
+}
+
+#[test]
+fn open_with_magic_detection_unrecognized() -> Result<()> {
+    let res = Archive::open_with_magic_detection("LICENSE");
+    assert!(matches!(res, Err(ArkivError::UnrecognizedFormat(_))));
+    let res = Archive::open_with_magic_detection("tests/sample/not_found");
+    assert!(matches!(res, Err(ArkivError::ArchiveNotFound(_))));
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "zip")]
+fn try_from() -> Result<()> {
+    use std::path::PathBuf;
+
+    let archive = Archive::try_from(PathBuf::from("tests/sample/sample.zip"))?;
+    assert_eq!(archive.format(), &arkiv::Format::Zip);
+    let archive: Archive = String::from("tests/sample/sample.zip").try_into()?;
+    assert_eq!(archive.format(), &arkiv::Format::Zip);
+
+    let paths = vec![PathBuf::from("tests/sample/sample.zip"); 2];
+    let archives = paths
+        .into_iter()
+        .map(Archive::try_from)
+        .collect::<arkiv::Result<Vec<_>>>()?;
+    assert_eq!(archives.len(), 2);
+
+    let res = Archive::try_from(String::from("tests/sample/not_found.zip"));
+    assert!(matches!(res, Err(ArkivError::ArchiveNotFound(_))));
+    Ok(())
+}
@@ -0,0 +1,117 @@
+use arkiv::Archive;
+use std::{
+    io::{read_to_string, Read},
+    path::Path,
+};
+
+type Error = Box<dyn std::error::Error>;
+type Result<T> = std::result::Result<T, Error>;
+
+#[allow(unused)]
+fn test(path: impl AsRef<Path>) -> Result<()> {
+    let mut archive = Archive::open(path)?;
+
+    let reader = archive.open_entry("sample/sample.txt")?;
+    assert_eq!(read_to_string(reader)?, "sample\n");
+
+    // call a second time to check that the entry is read from the start again
+    let reader = archive.open_entry("sample/sample.txt")?;
+    assert_eq!(read_to_string(reader)?, "sample\n");
+
+    assert!(matches!(
+        archive.open_entry("sample/not_found"),
+        Err(arkiv::Error::FileNotFound)
+    ));
+    assert!(matches!(
+        archive.open_entry("sample/"),
+        Err(arkiv::Error::EntryTypeNotSupported(_))
+    ));
+
+    Ok(())
+}
+
+#[allow(unused)]
+fn test_large_entry(path: impl AsRef<Path>) -> Result<()> {
+    const SIZE: usize = 4 * 1024 * 1024;
+
+    let mut archive = Archive::open(path)?;
+    let mut reader = archive.open_entry("large/data.bin")?;
+
+    // the entry contains the bytes 0, 1, ..., 250, 0, 1, ...
+    let mut chunk = vec![0; 64 * 1024];
+    let mut offset = 0;
+    loop {
+        let len = reader.read(&mut chunk)?;
+        if len == 0 {
+            break;
+        }
+        for (i, byte) in chunk[..len].iter().enumerate() {
+            assert_eq!(*byte as usize, (offset + i) % 251);
+        }
+        offset += len;
+    }
+    assert_eq!(offset, SIZE);
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "zip")]
+fn zip_archive() -> Result<()> {
+    test("tests/sample/sample.zip")
+}
+
+#[test]
+#[cfg(all(feature = "gzip", feature = "tar"))]
+fn tar_gz_archive() -> Result<()> {
+    test("tests/sample/sample.tar.gz")?;
+    test("tests/sample/sample.tgz")
+}
+
+#[test]
+#[cfg(all(feature = "bzip", feature = "tar"))]
+fn tar_bz2_archive() -> Result<()> {
+    test("tests/sample/sample.tar.bz2")
+}
+
+#[test]
+#[cfg(all(feature = "xz", feature = "tar"))]
+fn tar_xz_archive() -> Result<()> {
+    test("tests/sample/sample.tar.xz")
+}
+
+#[test]
+#[cfg(all(feature = "zstd", feature = "tar"))]
+fn tar_zstd_archive() -> Result<()> {
+    test("tests/sample/sample.tar.zstd")?;
+    test("tests/sample/sample.tar.zst")
+}
+
+#[test]
+#[cfg(feature = "tar")]
+fn gem_archive() -> Result<()> {
+    test("tests/sample/sample.gem")
+}
+
+#[test]
+#[cfg(feature = "crx")]
+fn crx_archive() -> Result<()> {
+    test("tests/sample/sample.crx")
+}
+
+#[test]
+#[cfg(feature = "sevenz")]
+fn sevenz_archive() -> Result<()> {
+    test("tests/sample/sample.7z")
+}
+
+#[test]
+#[cfg(feature = "zip")]
+fn zip_large_entry() -> Result<()> {
+    test_large_entry("tests/sample/large-entry.zip")
+}
+
+#[test]
+#[cfg(all(feature = "gzip", feature = "tar"))]
+fn tar_gz_large_entry() -> Result<()> {
+    test_large_entry("tests/sample/large-entry.tar.gz")
+}
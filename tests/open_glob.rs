@@ -0,0 +1,49 @@
+#[cfg(feature = "glob")]
+mod open_glob {
+    use arkiv::Archive;
+
+    type Error = Box<dyn std::error::Error>;
+    type Result<T> = std::result::Result<T, Error>;
+
+    #[test]
+    #[cfg(all(feature = "tar", feature = "zstd"))]
+    fn matching_files() -> Result<()> {
+        let archives = Archive::open_glob("tests/sample/sample.tar.zst*")?;
+        let mut actual: Vec<String> = archives
+            .iter()
+            .map(|archive| archive.path().display().to_string())
+            .collect();
+        actual.sort();
+        assert_eq!(
+            actual,
+            vec![
+                "tests/sample/sample.tar.zst",
+                "tests/sample/sample.tar.zstd"
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn no_matching_files() -> Result<()> {
+        let archives = Archive::open_glob("tests/sample/*.does-not-exist")?;
+        assert!(archives.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_pattern() {
+        let result = Archive::open_glob("tests/sample/***.zip");
+        assert!(matches!(result, Err(arkiv::Error::InvalidPattern(_))));
+    }
+
+    #[test]
+    #[cfg(feature = "tar")]
+    fn open_matching_archive() -> Result<()> {
+        let mut archives = Archive::open_glob("tests/sample/*.gem")?;
+        assert_eq!(archives.len(), 1);
+        assert_eq!(archives[0].format(), &arkiv::Format::Tar);
+        assert_eq!(archives[0].entry_paths()?.len(), 2);
+        Ok(())
+    }
+}
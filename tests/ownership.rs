@@ -0,0 +1,53 @@
+use arkiv::Archive;
+
+type Error = Box<dyn std::error::Error>;
+type Result<T> = std::result::Result<T, Error>;
+
+type Ownership = (Option<u64>, Option<u64>, Option<String>, Option<String>);
+
+#[allow(unused)]
+fn ownership(archive: &mut Archive, entry_path: &str) -> Result<Ownership> {
+    let entry = archive.entry_by_name(entry_path)?;
+    Ok((
+        entry.uid(),
+        entry.gid(),
+        entry.uname().map(String::from),
+        entry.gname().map(String::from),
+    ))
+}
+
+#[test]
+#[cfg(feature = "tar")]
+fn tar_archive() -> Result<()> {
+    let mut archive = Archive::open("tests/sample/ownership.tar")?;
+    assert_eq!(
+        ownership(&mut archive, "sample/")?,
+        (Some(0), Some(0), Some("root".into()), Some("root".into()))
+    );
+    assert_eq!(
+        ownership(&mut archive, "sample/owned.txt")?,
+        (
+            Some(1000),
+            Some(100),
+            Some("builder".into()),
+            Some("users".into())
+        )
+    );
+    // empty user and group names are not reported
+    assert_eq!(
+        ownership(&mut archive, "sample/anonymous.txt")?,
+        (Some(65534), Some(65534), None, None)
+    );
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "zip")]
+fn zip_archive() -> Result<()> {
+    let mut archive = Archive::open("tests/sample/sample.zip")?;
+    assert_eq!(
+        ownership(&mut archive, "sample/sample.txt")?,
+        (None, None, None, None)
+    );
+    Ok(())
+}
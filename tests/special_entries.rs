@@ -0,0 +1,77 @@
+#[cfg(feature = "tar")]
+mod special_entries {
+    use arkiv::{Archive, ArchiveOptions, EntryType};
+    use std::{fs::read_to_string, path::Path};
+
+    type Error = Box<dyn std::error::Error>;
+    type Result<T> = std::result::Result<T, Error>;
+
+    const PATH: &str = "tests/sample/hardlink.tar";
+
+    #[test]
+    fn entries() -> Result<()> {
+        let mut archive = Archive::open(PATH)?;
+
+        let hardlink = archive.entry_by_name("sample/hardlink.txt")?;
+        assert!(hardlink.is_hardlink());
+        assert!(!hardlink.is_file());
+        assert!(!hardlink.is_other());
+        assert_eq!(
+            hardlink.hardlink_target(),
+            Some(Path::new("sample/sample.txt"))
+        );
+        assert_eq!(hardlink.symlink_target(), None);
+
+        let fifo = archive.entry_by_name("sample/fifo")?;
+        assert!(fifo.is_other());
+        assert!(!fifo.is_hardlink());
+        assert_eq!(fifo.hardlink_target(), None);
+
+        let sample = archive.entry_by_name("sample/sample.txt")?;
+        assert!(!sample.is_other());
+        assert!(!sample.is_hardlink());
+        Ok(())
+    }
+
+    #[test]
+    fn unpack() -> Result<()> {
+        let sandbox = tempfile::tempdir()?;
+        let mut archive = Archive::open(PATH)?;
+        archive.unpack(&sandbox)?;
+
+        let hardlink = sandbox.path().join("sample/hardlink.txt");
+        assert_eq!(read_to_string(&hardlink)?, "sample\n");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+            let sample = sandbox.path().join("sample/sample.txt");
+            assert_eq!(hardlink.metadata()?.ino(), sample.metadata()?.ino());
+        }
+
+        // the FIFO is skipped
+        assert!(sandbox
+            .path()
+            .join("sample/fifo")
+            .symlink_metadata()
+            .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn unpack_strict() -> Result<()> {
+        let sandbox = tempfile::tempdir()?;
+        let options = ArchiveOptions {
+            strict_entry_types: true,
+            ..Default::default()
+        };
+        let mut archive = Archive::open(PATH)?.with_options(options);
+        assert!(matches!(
+            archive.unpack(&sandbox),
+            Err(arkiv::Error::EntryTypeNotSupported(EntryType::Other))
+        ));
+
+        // nothing is unpacked
+        assert!(!sandbox.path().join("sample").exists());
+        Ok(())
+    }
+}
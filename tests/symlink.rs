@@ -0,0 +1,70 @@
+use arkiv::Archive;
+use std::path::Path;
+
+type Error = Box<dyn std::error::Error>;
+type Result<T> = std::result::Result<T, Error>;
+
+#[allow(unused)]
+fn test(path: impl AsRef<Path>) -> Result<()> {
+    let mut archive = Archive::open(path)?;
+
+    let link = archive.entry_by_name("sample/link.txt")?;
+    assert!(link.is_symlink());
+    assert!(!link.is_file());
+    assert!(!link.is_dir());
+    assert_eq!(link.symlink_target(), Some(Path::new("sample.txt")));
+
+    let sample = archive.entry_by_name("sample/sample.txt")?;
+    assert!(!sample.is_symlink());
+    assert_eq!(sample.symlink_target(), None);
+
+    #[cfg(unix)]
+    {
+        use std::fs::{read_link, read_to_string};
+
+        let sandbox = tempfile::tempdir()?;
+        archive.unpack(&sandbox)?;
+        let outpath = sandbox.path().join("sample/link.txt");
+        assert_eq!(read_link(&outpath)?, Path::new("sample.txt"));
+        assert_eq!(read_to_string(&outpath)?, "sample\n");
+
+        // unpacking the link again replaces it
+        archive.unpack_entry(&link, &sandbox)?;
+        assert_eq!(read_link(&outpath)?, Path::new("sample.txt"));
+    }
+
+    Ok(())
+}
+
+#[allow(unused)]
+fn test_unsafe(path: impl AsRef<Path>) -> Result<()> {
+    let mut archive = Archive::open(path)?;
+    let link = archive.find(|entry| entry.is_symlink())?.next().unwrap()?;
+
+    let sandbox = tempfile::tempdir()?;
+    assert!(matches!(
+        archive.unpack(&sandbox),
+        Err(arkiv::Error::InvalidArchive { .. })
+    ));
+    assert!(matches!(
+        archive.unpack_entry(&link, &sandbox),
+        Err(arkiv::Error::InvalidArchive { .. })
+    ));
+    assert!(sandbox.path().join(link.path()).symlink_metadata().is_err());
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "zip")]
+fn zip_archive() -> Result<()> {
+    test("tests/sample/symlink.zip")?;
+    test_unsafe("tests/sample/symlink-traversal.zip")
+}
+
+#[test]
+#[cfg(feature = "tar")]
+fn tar_archive() -> Result<()> {
+    test("tests/sample/symlink.tar")?;
+    test_unsafe("tests/sample/symlink-absolute.tar")
+}
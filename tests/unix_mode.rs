@@ -0,0 +1,39 @@
+use arkiv::Archive;
+use std::path::Path;
+
+type Error = Box<dyn std::error::Error>;
+type Result<T> = std::result::Result<T, Error>;
+
+#[allow(unused)]
+fn test(path: impl AsRef<Path>) -> Result<()> {
+    let mut archive = Archive::open(path)?;
+    assert_eq!(archive.entry_by_name("bin/")?.unix_mode(), Some(0o755));
+    assert_eq!(archive.entry_by_name("bin/tool")?.unix_mode(), Some(0o755));
+    assert_eq!(
+        archive.entry_by_name("bin/README")?.unix_mode(),
+        Some(0o644)
+    );
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "zip")]
+fn zip_archive() -> Result<()> {
+    test("tests/sample/executable.zip")
+}
+
+#[test]
+#[cfg(feature = "zip")]
+fn zip_archive_without_unix_mode() -> Result<()> {
+    let mut archive = Archive::open("tests/sample/scripts.zip")?;
+    for entry in archive.entries_iter()? {
+        assert_eq!(entry?.unix_mode(), None);
+    }
+    Ok(())
+}
+
+#[test]
+#[cfg(all(feature = "gzip", feature = "tar"))]
+fn tar_gz_archive() -> Result<()> {
+    test("tests/sample/executable.tar.gz")
+}
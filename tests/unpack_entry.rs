@@ -54,3 +54,54 @@ fn tar_zst_archive() -> Result<()> {
     test("tests/sample/sample.tar.zstd")?;
     test("tests/sample/sample.tar.zst")
 }
+
+#[test]
+#[cfg(all(feature = "lzip", feature = "tar"))]
+fn tar_lz_archive() -> Result<()> {
+    test("tests/sample/sample.tar.lz")
+}
+
+#[test]
+#[cfg(feature = "tar")]
+fn gem_archive() -> Result<()> {
+    test("tests/sample/sample.gem")
+}
+
+#[test]
+#[cfg(feature = "crx")]
+fn crx_archive() -> Result<()> {
+    test("tests/sample/sample.crx")
+}
+
+#[test]
+#[cfg(feature = "sevenz")]
+fn sevenz_archive() -> Result<()> {
+    test("tests/sample/sample.7z")
+}
+
+#[test]
+#[cfg(feature = "tar")]
+fn other_entry_type() -> Result<()> {
+    let sandbox = tempfile::tempdir()?;
+    let mut archive = Archive::open("tests/sample/special.tar")?;
+
+    let special = archive.entry_by_name("special/")?;
+    archive.unpack_entry(&special, &sandbox)?;
+    assert!(sandbox.path().join("special").is_dir());
+
+    let fifo = archive.entry_by_name("special/fifo")?;
+    assert!(!fifo.is_file());
+    assert!(!fifo.is_dir());
+    assert!(matches!(
+        archive.unpack_entry(&fifo, &sandbox),
+        Err(arkiv::Error::EntryTypeNotSupported(arkiv::EntryType::Other))
+    ));
+
+    Ok(())
+}
+
+#[test]
+#[cfg(all(feature = "rpm", feature = "gzip"))]
+fn rpm_archive() -> Result<()> {
+    test("tests/sample/sample.rpm")
+}
@@ -0,0 +1,89 @@
+use arkiv::Archive;
+use std::{
+    fs::{read_dir, read_to_string},
+    path::Path,
+};
+
+type Error = Box<dyn std::error::Error>;
+type Result<T> = std::result::Result<T, Error>;
+
+#[allow(unused)]
+fn test(path: impl AsRef<Path>) -> Result<()> {
+    let mut archive = Archive::open(path)?;
+
+    // only the files, their parent directory is created anyway
+    let sandbox = tempfile::tempdir()?;
+    let mut visited = vec![];
+    archive.unpack_matching(&sandbox, |entry| {
+        visited.push(entry.path().to_path_buf());
+        entry.is_file()
+    })?;
+    assert_eq!(visited.len(), 2);
+    assert_eq!(
+        read_to_string(sandbox.path().join("sample/sample.txt"))?,
+        "sample\n"
+    );
+
+    // nothing matches
+    let sandbox = tempfile::tempdir()?;
+    archive.unpack_matching(&sandbox, |_| false)?;
+    assert_eq!(read_dir(&sandbox)?.count(), 0);
+
+    // only the directories
+    let sandbox = tempfile::tempdir()?;
+    archive.unpack_matching(&sandbox, |entry| entry.is_dir())?;
+    assert!(sandbox.path().join("sample").is_dir());
+    assert!(!sandbox.path().join("sample/sample.txt").exists());
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "zip")]
+fn zip_archive() -> Result<()> {
+    test("tests/sample/sample.zip")
+}
+
+#[test]
+#[cfg(all(feature = "gzip", feature = "tar"))]
+fn tar_gz_archive() -> Result<()> {
+    test("tests/sample/sample.tar.gz")?;
+    test("tests/sample/sample.tgz")
+}
+
+#[test]
+#[cfg(all(feature = "bzip", feature = "tar"))]
+fn tar_bz2_archive() -> Result<()> {
+    test("tests/sample/sample.tar.bz2")
+}
+
+#[test]
+#[cfg(all(feature = "xz", feature = "tar"))]
+fn tar_xz_archive() -> Result<()> {
+    test("tests/sample/sample.tar.xz")
+}
+
+#[test]
+#[cfg(all(feature = "zstd", feature = "tar"))]
+fn tar_zstd_archive() -> Result<()> {
+    test("tests/sample/sample.tar.zstd")?;
+    test("tests/sample/sample.tar.zst")
+}
+
+#[test]
+#[cfg(feature = "tar")]
+fn gem_archive() -> Result<()> {
+    test("tests/sample/sample.gem")
+}
+
+#[test]
+#[cfg(feature = "crx")]
+fn crx_archive() -> Result<()> {
+    test("tests/sample/sample.crx")
+}
+
+#[test]
+#[cfg(feature = "sevenz")]
+fn sevenz_archive() -> Result<()> {
+    test("tests/sample/sample.7z")
+}
@@ -0,0 +1,114 @@
+use arkiv::Archive;
+#[cfg(any(feature = "zip", feature = "tar"))]
+use arkiv::{ArchiveBuilder, Format};
+use std::{
+    fs::{metadata, read_to_string},
+    path::Path,
+    time::{Duration, SystemTime},
+};
+
+type Error = Box<dyn std::error::Error>;
+type Result<T> = std::result::Result<T, Error>;
+
+#[allow(unused)]
+fn test(path: impl AsRef<Path>) -> Result<()> {
+    let sandbox = tempfile::tempdir()?;
+    let fixed_time = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000_000);
+    let mut archive = Archive::open(path)?;
+    archive.unpack_reproducible(&sandbox, fixed_time)?;
+
+    let sample = sandbox.path().join("sample/sample.txt");
+    assert_eq!(read_to_string(&sample)?, "sample\n");
+    assert_eq!(metadata(&sample)?.modified()?, fixed_time);
+    let dir = sandbox.path().join("sample");
+    assert_eq!(metadata(&dir)?.modified()?, fixed_time);
+
+    /// call a second time to check that the rewind is done properly
+    assert!(archive.unpack_reproducible(&sandbox, fixed_time).is_ok());
+    assert_eq!(metadata(&sample)?.modified()?, fixed_time);
+
+    Ok(())
+}
+
+/// checks that the entries are unpacked in sorted path order rather than in
+/// the order of the archive, using an archive in which the file `a` conflicts
+/// with the directory of `a/b.txt`: in sorted order, `a` is unpacked first,
+/// unpacking `a/b.txt` then fails and `z.txt` is never unpacked.
+#[cfg(any(feature = "zip", feature = "tar"))]
+fn test_order(format: Format) -> Result<()> {
+    let mut builder = ArchiveBuilder::new(format.clone());
+    builder
+        .add_bytes(Path::new("z.txt"), b"z\n")?
+        .add_bytes(Path::new("a/b.txt"), b"b\n")?
+        .add_bytes(Path::new("a"), b"a\n")?;
+    let mut archive = Archive::from_bytes(builder.finish_to_bytes()?, format)?;
+
+    let sandbox = tempfile::tempdir()?;
+    let fixed_time = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000_000);
+    assert!(archive.unpack_reproducible(&sandbox, fixed_time).is_err());
+    assert_eq!(read_to_string(sandbox.path().join("a"))?, "a\n");
+    assert!(!sandbox.path().join("z.txt").exists());
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "zip")]
+fn zip_archive_order() -> Result<()> {
+    test_order(Format::Zip)
+}
+
+#[test]
+#[cfg(feature = "tar")]
+fn tar_archive_order() -> Result<()> {
+    test_order(Format::Tar)
+}
+
+#[test]
+#[cfg(feature = "zip")]
+fn zip_archive() -> Result<()> {
+    test("tests/sample/sample.zip")
+}
+
+#[test]
+#[cfg(all(feature = "gzip", feature = "tar"))]
+fn tar_gz_archive() -> Result<()> {
+    test("tests/sample/sample.tar.gz")?;
+    test("tests/sample/sample.tgz")
+}
+
+#[test]
+#[cfg(all(feature = "bzip", feature = "tar"))]
+fn tar_bz2_archive() -> Result<()> {
+    test("tests/sample/sample.tar.bz2")
+}
+
+#[test]
+#[cfg(all(feature = "xz", feature = "tar"))]
+fn tar_xz_archive() -> Result<()> {
+    test("tests/sample/sample.tar.xz")
+}
+
+#[test]
+#[cfg(all(feature = "zstd", feature = "tar"))]
+fn tar_zstd_archive() -> Result<()> {
+    test("tests/sample/sample.tar.zstd")?;
+    test("tests/sample/sample.tar.zst")
+}
+
+#[test]
+#[cfg(feature = "tar")]
+fn gem_archive() -> Result<()> {
+    test("tests/sample/sample.gem")
+}
+
+#[test]
+#[cfg(feature = "crx")]
+fn crx_archive() -> Result<()> {
+    test("tests/sample/sample.crx")
+}
+
+#[test]
+#[cfg(feature = "sevenz")]
+fn sevenz_archive() -> Result<()> {
+    test("tests/sample/sample.7z")
+}
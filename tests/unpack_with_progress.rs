@@ -0,0 +1,83 @@
+use arkiv::Archive;
+use std::{fs::read_to_string, path::Path};
+
+type Error = Box<dyn std::error::Error>;
+type Result<T> = std::result::Result<T, Error>;
+
+#[allow(unused)]
+fn test(path: impl AsRef<Path>) -> Result<()> {
+    let sandbox = tempfile::tempdir()?;
+    let mut archive = Archive::open(path)?;
+
+    let mut progress = vec![];
+    archive.unpack_with_progress(&sandbox, |done, total| progress.push((done, total)))?;
+    assert_eq!(progress, vec![(0, 2), (1, 2), (2, 2)]);
+
+    assert_eq!(
+        read_to_string(sandbox.path().join("sample/sample.txt"))?,
+        "sample\n"
+    );
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "zip")]
+fn zip_archive() -> Result<()> {
+    test("tests/sample/sample.zip")
+}
+
+#[test]
+#[cfg(all(feature = "gzip", feature = "tar"))]
+fn tar_gz_archive() -> Result<()> {
+    test("tests/sample/sample.tar.gz")?;
+    test("tests/sample/sample.tgz")
+}
+
+#[test]
+#[cfg(all(feature = "bzip", feature = "tar"))]
+fn tar_bz2_archive() -> Result<()> {
+    test("tests/sample/sample.tar.bz2")
+}
+
+#[test]
+#[cfg(all(feature = "xz", feature = "tar"))]
+fn tar_xz_archive() -> Result<()> {
+    test("tests/sample/sample.tar.xz")
+}
+
+#[test]
+#[cfg(all(feature = "zstd", feature = "tar"))]
+fn tar_zstd_archive() -> Result<()> {
+    test("tests/sample/sample.tar.zstd")?;
+    test("tests/sample/sample.tar.zst")
+}
+
+#[test]
+#[cfg(feature = "tar")]
+fn gem_archive() -> Result<()> {
+    test("tests/sample/sample.gem")
+}
+
+#[test]
+#[cfg(feature = "crx")]
+fn crx_archive() -> Result<()> {
+    test("tests/sample/sample.crx")
+}
+
+#[test]
+#[cfg(feature = "sevenz")]
+fn sevenz_archive() -> Result<()> {
+    test("tests/sample/sample.7z")
+}
+
+#[test]
+#[cfg(feature = "gzip")]
+fn gz_file() -> Result<()> {
+    let sandbox = tempfile::tempdir()?;
+    let mut archive = Archive::open("tests/sample/sample.txt.gz")?;
+
+    let mut progress = vec![];
+    archive.unpack_with_progress(&sandbox, |done, total| progress.push((done, total)))?;
+    assert_eq!(progress, vec![(0, 1), (1, 1)]);
+    Ok(())
+}
@@ -0,0 +1,93 @@
+use arkiv::{Archive, Unmatched};
+use std::{
+    fs::{read_dir, read_to_string},
+    path::Path,
+};
+
+type Error = Box<dyn std::error::Error>;
+type Result<T> = std::result::Result<T, Error>;
+
+#[allow(unused)]
+fn test(path: impl AsRef<Path>) -> Result<()> {
+    let mut archive = Archive::open(path)?;
+
+    // the top-level directory is stripped
+    let sandbox = tempfile::tempdir()?;
+    archive.unpack_with_strip_prefix(&sandbox, "sample", Unmatched::Skip)?;
+    assert_eq!(
+        read_to_string(sandbox.path().join("sample.txt"))?,
+        "sample\n"
+    );
+    assert!(!sandbox.path().join("sample").exists());
+
+    // only the entries under the prefix are stripped
+    let sandbox = tempfile::tempdir()?;
+    archive.unpack_with_strip_prefix(&sandbox, "sample/sample.txt", Unmatched::Keep)?;
+    assert!(sandbox.path().join("sample").is_dir());
+    assert!(!sandbox.path().join("sample/sample.txt").exists());
+
+    let sandbox = tempfile::tempdir()?;
+    archive.unpack_with_strip_prefix(&sandbox, "sample/sample.txt", Unmatched::Skip)?;
+    assert_eq!(read_dir(&sandbox)?.count(), 0);
+
+    // no entry under the prefix, nothing is unpacked
+    let sandbox = tempfile::tempdir()?;
+    let dest = sandbox.path().join("dest");
+    match archive.unpack_with_strip_prefix(&dest, "missing", Unmatched::Keep) {
+        Err(arkiv::Error::Io(e)) => assert_eq!(e.kind(), std::io::ErrorKind::NotFound),
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert!(!dest.exists());
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "zip")]
+fn zip_archive() -> Result<()> {
+    test("tests/sample/sample.zip")
+}
+
+#[test]
+#[cfg(all(feature = "gzip", feature = "tar"))]
+fn tar_gz_archive() -> Result<()> {
+    test("tests/sample/sample.tar.gz")?;
+    test("tests/sample/sample.tgz")
+}
+
+#[test]
+#[cfg(all(feature = "bzip", feature = "tar"))]
+fn tar_bz2_archive() -> Result<()> {
+    test("tests/sample/sample.tar.bz2")
+}
+
+#[test]
+#[cfg(all(feature = "xz", feature = "tar"))]
+fn tar_xz_archive() -> Result<()> {
+    test("tests/sample/sample.tar.xz")
+}
+
+#[test]
+#[cfg(all(feature = "zstd", feature = "tar"))]
+fn tar_zstd_archive() -> Result<()> {
+    test("tests/sample/sample.tar.zstd")?;
+    test("tests/sample/sample.tar.zst")
+}
+
+#[test]
+#[cfg(feature = "tar")]
+fn gem_archive() -> Result<()> {
+    test("tests/sample/sample.gem")
+}
+
+#[test]
+#[cfg(feature = "crx")]
+fn crx_archive() -> Result<()> {
+    test("tests/sample/sample.crx")
+}
+
+#[test]
+#[cfg(feature = "sevenz")]
+fn sevenz_archive() -> Result<()> {
+    test("tests/sample/sample.7z")
+}
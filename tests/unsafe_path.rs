@@ -0,0 +1,46 @@
+use arkiv::Archive;
+use std::path::Path;
+
+type Error = Box<dyn std::error::Error>;
+type Result<T> = std::result::Result<T, Error>;
+
+#[allow(unused)]
+fn test(path: impl AsRef<Path>) -> Result<()> {
+    let sandbox = tempfile::tempdir()?;
+    let dest = sandbox.path().join("dest");
+    let mut archive = Archive::open(path)?;
+
+    // the entry is listed as is
+    assert_eq!(
+        archive.entry_paths()?,
+        vec!["sample/", "sample/sample.txt", "sample/../../traversal.txt"]
+    );
+
+    let res = archive.unpack(&dest);
+    assert!(matches!(res, Err(arkiv::Error::UnsafePath(_))));
+    assert!(!sandbox.path().join("traversal.txt").exists());
+
+    let traversal = archive.entry_by_name("sample/../../traversal.txt")?;
+    let res = archive.unpack_entry(&traversal, &dest);
+    assert!(matches!(res, Err(arkiv::Error::UnsafePath(_))));
+    assert!(!sandbox.path().join("traversal.txt").exists());
+
+    // the other entries can still be unpacked individually
+    let sample = archive.entry_by_name("sample/sample.txt")?;
+    archive.unpack_entry(&sample, &dest)?;
+    assert!(dest.join("sample/sample.txt").is_file());
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "zip")]
+fn zip_archive() -> Result<()> {
+    test("tests/sample/traversal.zip")
+}
+
+#[test]
+#[cfg(feature = "tar")]
+fn tar_archive() -> Result<()> {
+    test("tests/sample/traversal.tar")
+}
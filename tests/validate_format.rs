@@ -0,0 +1,46 @@
+use arkiv::{Archive, ArchiveOptions, Format};
+
+type Error = Box<dyn std::error::Error>;
+type Result<T> = std::result::Result<T, Error>;
+
+#[allow(unused)]
+fn open(path: &str, format: Format) -> Result<Archive> {
+    let options = ArchiveOptions {
+        validate_format: true,
+        ..Default::default()
+    };
+    Ok(Archive::open_with_format(path, format)?.with_options(options))
+}
+
+#[test]
+#[cfg(all(feature = "gzip", feature = "tar"))]
+fn matching_format() -> Result<()> {
+    let mut archive = open("tests/sample/sample.tar.gz", Format::TarGzip)?;
+    assert_eq!(archive.entry_paths()?.len(), 2);
+    Ok(())
+}
+
+#[test]
+#[cfg(all(feature = "zip", feature = "gzip"))]
+fn mismatching_format() -> Result<()> {
+    let mut archive = open("tests/sample/sample.tar.gz", Format::Zip)?;
+    match archive.entry_paths() {
+        Err(arkiv::Error::InvalidArchive { path, message }) => {
+            assert_eq!(
+                path.as_deref(),
+                Some(std::path::Path::new("tests/sample/sample.tar.gz"))
+            );
+            assert!(message.contains("Zip"));
+        }
+        other => panic!("unexpected result: {other:?}"),
+    }
+    Ok(())
+}
+
+#[test]
+#[cfg(all(feature = "gzip", feature = "tar"))]
+fn disabled_by_default() -> Result<()> {
+    let archive = Archive::open("tests/sample/sample.tar.gz")?;
+    assert!(!archive.options().validate_format);
+    Ok(())
+}
@@ -0,0 +1,88 @@
+use arkiv::Archive;
+use std::path::Path;
+
+type Error = Box<dyn std::error::Error>;
+type Result<T> = std::result::Result<T, Error>;
+
+#[allow(unused)]
+fn test(path: impl AsRef<Path>) -> Result<()> {
+    let mut archive = Archive::open(path)?;
+    let dir = archive.entry_by_name("sample/")?;
+    let file = archive.entry_by_name("sample/sample.txt")?;
+    assert!(archive.verify_entry(&dir)?);
+    assert!(archive.verify_entry(&file)?);
+    Ok(())
+}
+
+#[allow(unused)]
+fn test_corrupted(path: impl AsRef<Path>, corrupted_path: impl AsRef<Path>) -> Result<()> {
+    let file = Archive::open(path)?.entry_by_name("sample/sample.txt")?;
+    let mut corrupted = Archive::open(corrupted_path)?;
+    assert!(!corrupted.verify_entry(&file)?);
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "zip")]
+fn zip_archive() -> Result<()> {
+    test("tests/sample/sample.zip")?;
+    test_corrupted("tests/sample/sample.zip", "tests/sample/sample-corrupt.zip")
+}
+
+#[test]
+#[cfg(all(feature = "gzip", feature = "tar"))]
+fn tar_gz_archive() -> Result<()> {
+    test("tests/sample/sample.tar.gz")?;
+    test("tests/sample/sample.tgz")
+}
+
+#[test]
+#[cfg(all(feature = "bzip", feature = "tar"))]
+fn tar_bz2_archive() -> Result<()> {
+    test("tests/sample/sample.tar.bz2")
+}
+
+#[test]
+#[cfg(all(feature = "xz", feature = "tar"))]
+fn tar_xz_archive() -> Result<()> {
+    test("tests/sample/sample.tar.xz")
+}
+
+#[test]
+#[cfg(all(feature = "zstd", feature = "tar"))]
+fn tar_zstd_archive() -> Result<()> {
+    test("tests/sample/sample.tar.zstd")?;
+    test("tests/sample/sample.tar.zst")
+}
+
+#[test]
+#[cfg(feature = "tar")]
+fn gem_archive() -> Result<()> {
+    test("tests/sample/sample.gem")?;
+    test_corrupted("tests/sample/sample.gem", "tests/sample/sample-corrupt.tar")
+}
+
+#[test]
+#[cfg(feature = "tar")]
+fn truncated_tar_archive() -> Result<()> {
+    let file = Archive::open("tests/sample/sample.gem")?.entry_by_name("sample/sample.txt")?;
+    // cut the archive right after the start of the header of the file
+    let mut data = std::fs::read("tests/sample/sample.gem")?;
+    data.truncate(512 + 4);
+    let mut truncated = Archive::from_reader(std::io::Cursor::new(data), arkiv::Format::Tar)?;
+    assert!(truncated.verify_entry(&file).is_err());
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "crx")]
+fn crx_archive() -> Result<()> {
+    test("tests/sample/sample.crx")
+}
+
+#[test]
+#[cfg(feature = "sevenz")]
+fn sevenz_archive() -> Result<()> {
+    test("tests/sample/sample.7z")?;
+    test_corrupted("tests/sample/sample.7z", "tests/sample/sample-corrupt.7z")
+}